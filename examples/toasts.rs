@@ -1,7 +1,8 @@
 //! # Toast Demo Example
 //!
 //! This example demonstrates the usage of the `toast` widget from the `egui_widget_ext` crate.
-//! It shows how to display toast notifications with different durations, colors, and messages.
+//! It shows how to post toast notifications with different durations, colors, and messages
+//! through the `ToastManager`, which owns the toast queue and renders it once per frame.
 //! Toasts are transient and disappear after a set duration.
 //!
 //! To run this example:
@@ -12,63 +13,298 @@
 use std::time::Duration;
 
 use eframe::egui;
-use egui::{Align2, Color32, Context, Vec2};
-use egui_widget_ext::Toast;
+use egui::{Color32, Context};
+use egui_widget_ext::{
+    AlertLevel, NotificationBell, NotificationCenter, Toast, ToastAction, ToastAnchor,
+    ToastAnimation, ToastEvent, ToastId, ToastManager, ToastPromise, ToastStacking,
+};
+
+/// Copy of the manager settings edited by the demo controls.
+struct StackOptions {
+    anchor: ToastAnchor,
+    stacking: ToastStacking,
+    offset: egui::Vec2,
+    animation: ToastAnimation,
+    reduce_motion: bool,
+    max_visible: Option<usize>,
+    overflow_summary: bool,
+}
+
+impl StackOptions {
+    fn load(ctx: &Context) -> Self {
+        ToastManager::with(ctx, |manager| Self {
+            anchor: manager.anchor,
+            stacking: manager.stacking,
+            offset: manager.offset,
+            animation: manager.animation,
+            reduce_motion: manager.reduce_motion,
+            max_visible: manager.max_visible,
+            overflow_summary: manager.overflow_summary,
+        })
+    }
+
+    fn store(self, ctx: &Context) {
+        ToastManager::with(ctx, |manager| {
+            manager.anchor = self.anchor;
+            manager.stacking = self.stacking;
+            manager.offset = self.offset;
+            manager.animation = self.animation;
+            manager.reduce_motion = self.reduce_motion;
+            manager.max_visible = self.max_visible;
+            manager.overflow_summary = self.overflow_summary;
+        });
+    }
+}
 
 struct ToastsApp {
-    toasts: Vec<Toast>,
+    upload: Option<ToastId>,
+    show_notifications: bool,
+    show_detached: bool,
 }
 
 impl ToastsApp {
     fn new(_cc: &eframe::CreationContext<'_>) -> Self {
-        Self { toasts: Vec::new() }
+        Self {
+            upload: None,
+            show_notifications: false,
+            show_detached: false,
+        }
     }
 }
 
 impl eframe::App for ToastsApp {
     fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("Toast Demo");
+            ui.horizontal(|ui| {
+                ui.heading("Toast Demo");
+                if ui.add(NotificationBell::new()).clicked() {
+                    self.show_notifications = !self.show_notifications;
+                }
+                ui.checkbox(&mut self.show_detached, "Detached panel");
+            });
             ui.label("Click a button to show a toast notification.");
+            ui.label(
+                "F6 focuses the newest toast, arrow keys move between toasts, Enter runs the first \
+                 action, Escape dismisses the focused toast and Shift+Escape dismisses all toasts.",
+            );
 
             ui.horizontal(|ui| {
                 if ui.button("Info Toast").clicked() {
-                    self.toasts.push(Toast::new("This is an info toast!"));
+                    ToastManager::add(ctx, Toast::info("This is an info toast!"));
                 }
                 if ui.button("Success Toast").clicked() {
-                    self.toasts.push(
-                        Toast::new("This is a success toast!")
-                            .with_color(Color32::from_rgb(76, 175, 80)),
+                    ToastManager::add(ctx, Toast::success("This is a success toast!"));
+                }
+                if ui.button("Warning Toast").clicked() {
+                    ToastManager::add(ctx, Toast::warning("This is a warning toast!"));
+                }
+                if ui.button("Error Toast").clicked() {
+                    ToastManager::add(ctx, Toast::error("This is an error toast!"));
+                }
+                if ui.button("Closeable Toast").clicked() {
+                    ToastManager::add(
+                        ctx,
+                        Toast::info("Close me with the button or click anywhere on me.")
+                            .can_close(true)
+                            .dismiss_on_click(true)
+                            .progress_bar(true),
+                    );
+                }
+                if ui.button("Undo Toast").clicked() {
+                    ToastManager::add(
+                        ctx,
+                        Toast::info("Message deleted")
+                            .with_action(ToastAction::new("undo", "Undo"))
+                            .with_action(ToastAction::new("view", "View").keep_open(true)),
+                    );
+                }
+                if ui.button("Burst of 20 Toasts").clicked() {
+                    for i in 1..=20 {
+                        ToastManager::add(ctx, Toast::error(&format!("Background job {i} failed")));
+                    }
+                }
+                if ui.button("Upload 20 Files").clicked() {
+                    for i in 1..=20 {
+                        ToastManager::add(
+                            ctx,
+                            Toast::success(&format!("file_{i}.png uploaded"))
+                                .group("upload-42")
+                                .group_label("files uploaded"),
+                        );
+                    }
+                }
+                if ui.button("Start Upload").clicked() {
+                    self.upload = Some(ToastManager::add(
+                        ctx,
+                        Toast::info("Uploading…").spinner(true),
+                    ));
+                }
+                let finish = self.upload.is_some() && ui.button("Finish Upload").clicked();
+                if let Some(upload) = self.upload.take_if(|_| finish) {
+                    ToastManager::update(ctx, upload, |toast| {
+                        toast
+                            .message("Uploaded")
+                            .with_level(AlertLevel::Success)
+                            .spinner(false)
+                    });
+                }
+                if ui.button("Background Job").clicked() {
+                    let (sender, receiver) = std::sync::mpsc::channel();
+                    std::thread::spawn(move || {
+                        std::thread::sleep(Duration::from_secs(3));
+                        let _ = sender.send(Ok("Background job finished".to_string()));
+                    });
+                    ToastManager::add(
+                        ctx,
+                        Toast::promise(
+                            "Running background job…",
+                            ToastPromise::from_receiver(receiver),
+                        ),
+                    );
+                }
+                if ui.button("Worker Thread Toasts").clicked() {
+                    let sender = ToastManager::sender(ctx);
+                    std::thread::spawn(move || {
+                        for step in 1..=3 {
+                            std::thread::sleep(Duration::from_secs(1));
+                            sender.send(Toast::info(&format!("Worker finished step {step} of 3")));
+                        }
+                    });
+                }
+                if ui.button("Custom Color Toast").clicked() {
+                    ToastManager::add(
+                        ctx,
+                        Toast::new("This toast has a custom color!")
+                            .with_color(Color32::from_rgb(200, 160, 255)),
                     );
                 }
                 if ui.button("8 sec Long Toast").clicked() {
-                    self.toasts.push(
+                    ToastManager::add(
+                        ctx,
                         Toast::new("This toast will last for 8 seconds!")
                             .duration(Duration::from_secs(8)),
                     );
                 }
+                if ui.button("Titled Toast").clicked() {
+                    ToastManager::add(
+                        ctx,
+                        Toast::warning("Only 2 GB left on the system disk.")
+                            .title("Storage almost full")
+                            .icon("💾"),
+                    );
+                }
+                if ui.button("Custom Toast").clicked() {
+                    ToastManager::add(
+                        ctx,
+                        Toast::custom(|ui| {
+                            ui.label("Version 2.0 is out.");
+                            ui.hyperlink_to(
+                                "Read the release notes",
+                                "https://github.com/emilk/egui",
+                            );
+                        })
+                        .title("What's new")
+                        .message("Version 2.0 is out"),
+                    );
+                }
+                if ui.button("Sticky Toast").clicked() {
+                    ToastManager::add(
+                        ctx,
+                        Toast::info("Update available")
+                            .persistent()
+                            .can_close(true)
+                            .with_action(ToastAction::new("restart", "Restart")),
+                    );
+                }
+            });
+
+            ui.separator();
+            // Edit a copy, so the manager is not locked while the widgets run.
+            let mut options = StackOptions::load(ctx);
+            egui::ComboBox::from_label("Anchor")
+                .selected_text(format!("{:?}", options.anchor))
+                .show_ui(ui, |ui| {
+                    for anchor in ToastAnchor::ALL {
+                        ui.selectable_value(&mut options.anchor, anchor, format!("{anchor:?}"));
+                    }
+                });
+            ui.horizontal(|ui| {
+                ui.radio_value(
+                    &mut options.stacking,
+                    ToastStacking::NewestOnTop,
+                    "Newest on top",
+                );
+                ui.radio_value(
+                    &mut options.stacking,
+                    ToastStacking::NewestOnBottom,
+                    "Newest on bottom",
+                );
+            });
+            ui.add(egui::Slider::new(&mut options.offset.x, 0.0..=100.0).text("Offset X"));
+            ui.add(egui::Slider::new(&mut options.offset.y, 0.0..=100.0).text("Offset Y"));
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut options.animation, ToastAnimation::Slide, "Slide");
+                ui.radio_value(&mut options.animation, ToastAnimation::Fade, "Fade");
+                ui.radio_value(&mut options.animation, ToastAnimation::None, "No animation");
+                ui.checkbox(&mut options.reduce_motion, "Reduce motion");
+            });
+            ui.horizontal(|ui| {
+                let mut max_visible = options.max_visible.unwrap_or(0);
+                ui.add(
+                    egui::Slider::new(&mut max_visible, 0..=10).text("Max visible (0 = no limit)"),
+                );
+                options.max_visible = (max_visible > 0).then_some(max_visible);
+                ui.checkbox(&mut options.overflow_summary, "Show \"+N more\"");
             });
+            options.store(ctx);
+        });
 
-            // Remove expired toasts
-            self.toasts.retain(|entry| !entry.has_expired());
+        egui::Window::new("Notification Center")
+            .open(&mut self.show_notifications)
+            .show(ctx, |ui| {
+                ui.add(NotificationCenter::new());
+            });
 
-            egui::Area::new(egui::Id::new("toasts_area"))
-                .anchor(Align2::RIGHT_BOTTOM, Vec2::new(0.0, 0.0))
-                .order(egui::Order::Foreground)
-                .show(ui.ctx(), |ui| {
-                    ui.set_width(350.0);
-                    ui.vertical(|ui| {
-                        for toast in &self.toasts {
-                            ui.add(toast.clone());
+        if self.show_detached {
+            ctx.show_viewport_immediate(
+                egui::ViewportId::from_hash_of("detached_panel"),
+                egui::ViewportBuilder::default()
+                    .with_title("Detached Panel")
+                    .with_inner_size([400.0, 300.0]),
+                |ctx, _class| {
+                    egui::CentralPanel::default().show(ctx, |ui| {
+                        if ui.button("Toast Here").clicked() {
+                            ToastManager::add(ctx, Toast::info("Shown in the detached panel"));
+                        }
+                        if ui.button("Toast in Main Window").clicked() {
+                            ToastManager::add(
+                                ctx,
+                                Toast::info("Sent from the detached panel")
+                                    .viewport(egui::ViewportId::ROOT),
+                            );
                         }
                     });
-                });
+                    if ctx.input(|input| input.viewport().close_requested()) {
+                        self.show_detached = false;
+                    }
+                    // Each viewport renders its own toast stack.
+                    ToastManager::show(ctx);
+                },
+            );
+        }
 
-            // Ensure toasts expire even if there's no user input
-            if !self.toasts.is_empty() {
-                ctx.request_repaint_after(Duration::from_millis(200));
+        ToastManager::show(ctx);
+        for event in ToastManager::events(ctx) {
+            if let ToastEvent::Action { action, .. } = event {
+                let message = match action.as_str() {
+                    "undo" => "Message restored",
+                    "view" => "Opening message",
+                    "restart" => "Restarting",
+                    _ => continue,
+                };
+                ToastManager::add(ctx, Toast::success(message));
             }
-        });
+        }
     }
 }
 
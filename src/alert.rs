@@ -4,6 +4,11 @@
 //! The alert box displays a message with a severity level (success, info, warning, error) and
 //! includes a close ("✕") button. The appearance of the alert can be customized via margins and corner radius.
 //!
+//! Alerts describe themselves to assistive technology with their message, and the close button is
+//! labelled "Close alert". With the `accesskit` feature an alert is also exposed as a live region, so
+//! screen readers announce it when it appears: politely for success and info, assertively for warnings
+//! and errors.
+//!
 //! ## Example
 //! ```
 //! # egui::__run_test_ui(|ui| {
@@ -19,7 +24,12 @@
 //! - [`Alert`]: Struct for configuring and displaying the alert widget.
 //! - [`alert`]: Convenience function for creating an alert widget.
 
-use egui::{Button, Color32, CornerRadius, Frame, Label, Margin, RichText, Stroke, Ui, Widget};
+use egui::{
+    Button, Color32, CornerRadius, Frame, Label, Margin, RichText, Stroke, Ui, Widget, WidgetInfo,
+    WidgetType,
+};
+#[cfg(feature = "accesskit")]
+use egui::{Context, Id};
 
 /// Represents the severity level of an alert. Determines the background color and semantic meaning
 /// of the alert box.
@@ -51,6 +61,8 @@ pub enum AlertLevel {
 pub struct Alert {
     /// The background color of the alert box.
     color: Color32,
+    /// The severity level of the alert, if one was set.
+    level: Option<AlertLevel>,
     /// The message displayed in the alert box.
     message: String,
     /// Padding inside the alert box.
@@ -68,6 +80,7 @@ impl Default for Alert {
     fn default() -> Self {
         Alert {
             color: Color32::from_rgb(255, 200, 200),
+            level: None,
             message: "No message provided".to_string(),
             inner_margin: 10,
             outer_margin: 10,
@@ -83,6 +96,7 @@ impl Alert {
         let color = Self::level_to_color(AlertLevel::Info);
         Self {
             color,
+            level: Some(AlertLevel::Info),
             message: message.to_string(),
             ..Default::default()
         }
@@ -90,6 +104,7 @@ impl Alert {
 
     /// Set the alert's severity level, which determines its background color.
     pub fn with_level(mut self, level: AlertLevel) -> Self {
+        self.level = Some(level);
         self.color = Self::level_to_color(level);
         self
     }
@@ -119,7 +134,9 @@ impl Alert {
     }
 
     /// Map an [`AlertLevel`] to its corresponding background color.
-    fn level_to_color(level: AlertLevel) -> Color32 {
+    ///
+    /// Shared with toasts so both widgets use the same palette.
+    pub(crate) fn level_to_color(level: AlertLevel) -> Color32 {
         match level {
             AlertLevel::Success => Color32::LIGHT_GREEN,
             AlertLevel::Info => Color32::LIGHT_BLUE,
@@ -127,6 +144,26 @@ impl Alert {
             AlertLevel::Error => Color32::LIGHT_RED,
         }
     }
+
+    /// Expose the widget with the given id as a live region, so screen readers announce it.
+    ///
+    /// Warnings and errors interrupt as assertive alerts, everything else waits as a polite status.
+    /// Shared with toasts so both widgets are announced alike.
+    #[cfg(feature = "accesskit")]
+    pub(crate) fn announce(ctx: &Context, id: Id, level: Option<AlertLevel>) {
+        use egui::accesskit::{Live, Role};
+
+        let urgent = matches!(level, Some(AlertLevel::Warning | AlertLevel::Error));
+        ctx.accesskit_node_builder(id, |node| {
+            if urgent {
+                node.set_role(Role::Alert);
+                node.set_live(Live::Assertive);
+            } else {
+                node.set_role(Role::Status);
+                node.set_live(Live::Polite);
+            }
+        });
+    }
 }
 
 impl Widget for Alert {
@@ -142,32 +179,37 @@ impl Widget for Alert {
             .inner_margin(Margin::same(self.inner_margin))
             .outer_margin(Margin::same(self.outer_margin));
 
-        frame
-            .show(ui, |ui| {
-                ui.horizontal(|ui| {
-                    let label_resp = ui
-                        .add(Label::new(RichText::new(&self.message).color(Color32::BLACK)).wrap());
-                    let response = if self.can_close {
-                        let close_resp = ui
-                            .with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                ui.add(
-                                    Button::new(
-                                        RichText::new("X").color(Color32::DARK_RED).strong(),
-                                    )
+        let shown = frame.show(ui, |ui| {
+            ui.horizontal(|ui| {
+                let label_resp =
+                    ui.add(Label::new(RichText::new(&self.message).color(Color32::BLACK)).wrap());
+                if self.can_close {
+                    let close_resp = ui
+                        .with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.add(
+                                Button::new(RichText::new("X").color(Color32::DARK_RED).strong())
                                     .frame(false),
-                                )
-                            })
-                            .inner;
-                        label_resp | close_resp
-                    } else {
-                        ui.add_space(ui.available_width());
-                        label_resp
-                    };
-                    response
-                })
-                .inner
+                            )
+                        })
+                        .inner;
+                    close_resp.widget_info(|| {
+                        WidgetInfo::labeled(WidgetType::Button, ui.is_enabled(), "Close alert")
+                    });
+                    label_resp | close_resp
+                } else {
+                    ui.add_space(ui.available_width());
+                    label_resp
+                }
             })
             .inner
+        });
+
+        shown
+            .response
+            .widget_info(|| WidgetInfo::labeled(WidgetType::Other, ui.is_enabled(), &self.message));
+        #[cfg(feature = "accesskit")]
+        Self::announce(ui.ctx(), shown.response.id, self.level);
+        shown.inner
     }
 }
 
@@ -6,7 +6,12 @@
 //! The intent is to have a feature for each widget and its associated functionality so that users can include only what they need.
 //! - `toggle_switch`: Simple toggle switch widget
 //! - `alert`: Widget for displaying alerts
+//! - `toast`: Toast notifications and a context-wide toast manager (enables `alert` for the shared severity levels)
+//! - `notification_center`: Notification center and bell badge listing the toast history (enables `toast`)
 //! - `all`: Enables all widgets provided by this crate
+//! - `accesskit`: Screen reader announcements for toasts and alerts through egui's AccessKit support
+//! - `log`: Bridge turning `log` records into toasts (enables `toast`)
+//! - `tracing`: `tracing_subscriber` layer turning events into toasts (enables `toast`)
 //!
 #[cfg(feature = "toggle_switch")]
 mod toggle_switch;
@@ -19,4 +24,25 @@ pub use alert::{Alert, AlertLevel, alert};
 #[cfg(feature = "toast")]
 mod toast;
 #[cfg(feature = "toast")]
-pub use toast::Toast;
+pub use toast::{
+    EguiClock, ManualClock, Toast, ToastAction, ToastClock, ToastPromise, ToastResponse,
+};
+#[cfg(feature = "toast")]
+mod toast_manager;
+#[cfg(feature = "toast")]
+pub use toast_manager::{
+    ToastAnchor, ToastAnimation, ToastEvent, ToastHistoryEntry, ToastId, ToastManager, ToastSender,
+    ToastStacking,
+};
+#[cfg(feature = "notification_center")]
+mod notification_center;
+#[cfg(feature = "notification_center")]
+pub use notification_center::{NotificationBell, NotificationCenter};
+#[cfg(feature = "log")]
+mod toast_log;
+#[cfg(feature = "log")]
+pub use toast_log::ToastLogger;
+#[cfg(feature = "tracing")]
+mod toast_tracing;
+#[cfg(feature = "tracing")]
+pub use toast_tracing::ToastLayer;
@@ -0,0 +1,260 @@
+//! # Notification Center Module
+//!
+//! This module provides widgets for browsing the toasts a user may have missed. The
+//! [`ToastManager`] keeps expired and dismissed toasts in a bounded history; the [`NotificationCenter`]
+//! lists that history with the level and age of each entry, and the [`NotificationBell`] is a button
+//! with a badge counting the unread entries.
+//!
+//! ## Usage
+//!
+//! Both widgets read the history of the manager stored in the `egui::Context`, so they need no state of
+//! their own. Clicking an entry of the notification center marks it as read, and its header has buttons
+//! to mark all entries as read and to clear the history. Toasts the user dismissed start out read, so
+//! the badge only counts the toasts that expired unnoticed.
+//!
+//! ## Example
+//! ```
+//! # egui::__run_test_ui(|ui| {
+//! use egui_widget_ext::{NotificationBell, NotificationCenter};
+//!
+//! let mut open = false;
+//! if ui.add(NotificationBell::new()).clicked() {
+//!     open = !open;
+//! }
+//! if open {
+//!     ui.add(NotificationCenter::new().max_height(200.0));
+//! }
+//! # });
+//! ```
+//!
+//! ## Components
+//! - [`NotificationCenter`]: Struct for displaying the toast history.
+//! - [`NotificationBell`]: Struct for displaying a bell button with an unread badge.
+
+use std::time::{Duration, SystemTime};
+
+use egui::{
+    Align, Button, Color32, CornerRadius, FontId, Frame, Label, Layout, Margin, Rect, Response,
+    RichText, ScrollArea, Sense, Ui, Widget, vec2,
+};
+
+use crate::{ToastHistoryEntry, ToastManager};
+
+/// A widget listing the expired and dismissed toasts of the [`ToastManager`], newest first.
+///
+/// Each entry shows the message, the level and how long ago the toast was posted. Unread entries are
+/// highlighted and marked as read when clicked.
+#[derive(Debug, Clone)]
+pub struct NotificationCenter {
+    /// Maximum height of the scrollable list of entries.
+    max_height: f32,
+    /// Text shown when the history is empty.
+    empty_text: String,
+}
+
+impl Default for NotificationCenter {
+    fn default() -> Self {
+        Self {
+            max_height: 300.0,
+            empty_text: "No notifications".to_string(),
+        }
+    }
+}
+
+impl NotificationCenter {
+    /// Create a notification center with the default height and empty text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum height of the list, which scrolls beyond it.
+    pub fn max_height(mut self, height: f32) -> Self {
+        self.max_height = height;
+        self
+    }
+
+    /// Set the text shown when the history is empty.
+    pub fn empty_text(mut self, text: &str) -> Self {
+        self.empty_text = text.to_string();
+        self
+    }
+}
+
+impl Widget for NotificationCenter {
+    /// Render the notification center and apply the read and clear requests made through it.
+    fn ui(self, ui: &mut Ui) -> Response {
+        let ctx = ui.ctx().clone();
+        // Copy the history out so the manager is not locked while rendering.
+        let (entries, unread) = ToastManager::with(&ctx, |manager| {
+            (manager.history().clone(), manager.unread_count())
+        });
+
+        let mut clear = false;
+        let mut mark_all = false;
+        let mut read = Vec::new();
+        let response = ui
+            .vertical(|ui| {
+                ui.horizontal(|ui| {
+                    ui.strong("Notifications");
+                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                        clear = ui
+                            .add_enabled(!entries.is_empty(), Button::new("Clear all"))
+                            .clicked();
+                        mark_all = ui
+                            .add_enabled(unread > 0, Button::new("Mark all read"))
+                            .clicked();
+                    });
+                });
+                ui.separator();
+
+                if entries.is_empty() {
+                    ui.weak(&self.empty_text);
+                    return;
+                }
+                let now = SystemTime::now();
+                ScrollArea::vertical()
+                    .max_height(self.max_height)
+                    .show(ui, |ui| {
+                        for entry in entries.iter().rev() {
+                            if entry_ui(ui, entry, now).clicked() {
+                                read.push(entry.id);
+                            }
+                        }
+                    });
+            })
+            .response;
+
+        if clear || mark_all || !read.is_empty() {
+            ToastManager::with(&ctx, |manager| {
+                if clear {
+                    manager.clear_history();
+                }
+                if mark_all {
+                    manager.mark_all_read();
+                }
+                for id in read {
+                    manager.mark_read(id);
+                }
+            });
+        }
+        response
+    }
+}
+
+/// Render a single history entry and return its click response.
+fn entry_ui(ui: &mut Ui, entry: &ToastHistoryEntry, now: SystemTime) -> Response {
+    let fill = if entry.read {
+        Color32::TRANSPARENT
+    } else {
+        ui.visuals().faint_bg_color
+    };
+    let frame = Frame::default()
+        .fill(fill)
+        .corner_radius(CornerRadius::same(4))
+        .inner_margin(Margin::symmetric(6, 4))
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.horizontal(|ui| {
+                let (dot, _) = ui.allocate_exact_size(vec2(8.0, 8.0), Sense::hover());
+                ui.painter().circle_filled(dot.center(), 4.0, entry.color);
+                ui.vertical(|ui| {
+                    if let Some(title) = &entry.title {
+                        ui.add(Label::new(RichText::new(title).strong()).wrap());
+                    }
+                    let message = if entry.repeat_count > 1 {
+                        format!("{}  ×{}", entry.message, entry.repeat_count)
+                    } else {
+                        entry.message.clone()
+                    };
+                    let mut text = RichText::new(message);
+                    if !entry.read {
+                        text = text.strong();
+                    }
+                    ui.add(Label::new(text).wrap());
+
+                    let age = format_age(now.duration_since(entry.posted).unwrap_or_default());
+                    let details = match entry.level {
+                        Some(level) => format!("{level:?} · {age}"),
+                        None => age,
+                    };
+                    ui.label(RichText::new(details).small().weak());
+                });
+            });
+        });
+    ui.interact(
+        frame.response.rect,
+        ui.id().with(("notification", entry.id)),
+        Sense::click(),
+    )
+}
+
+/// Format how long ago something happened, in the coarsest fitting unit.
+fn format_age(age: Duration) -> String {
+    let seconds = age.as_secs();
+    match seconds {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{} min ago", seconds / 60),
+        3600..86400 => format!("{} h ago", seconds / 3600),
+        _ => format!("{} d ago", seconds / 86400),
+    }
+}
+
+/// A bell button with a badge showing the number of unread notifications of the [`ToastManager`].
+///
+/// The badge is hidden while everything has been read. Use the returned response to open a
+/// [`NotificationCenter`].
+#[derive(Debug, Clone)]
+pub struct NotificationBell {
+    /// Text or emoji shown on the button.
+    icon: String,
+}
+
+impl Default for NotificationBell {
+    fn default() -> Self {
+        Self {
+            icon: "🔔".to_string(),
+        }
+    }
+}
+
+impl NotificationBell {
+    /// Create a bell button with the default bell icon.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the text or emoji shown on the button.
+    pub fn icon(mut self, icon: &str) -> Self {
+        self.icon = icon.to_string();
+        self
+    }
+}
+
+impl Widget for NotificationBell {
+    /// Render the bell button and paint the unread badge over its top right corner.
+    fn ui(self, ui: &mut Ui) -> Response {
+        let unread = ToastManager::with(ui.ctx(), |manager| manager.unread_count());
+        let response = ui.add(Button::new(RichText::new(self.icon).size(18.0)).frame(false));
+        if unread == 0 {
+            return response;
+        }
+
+        let text = if unread > 99 {
+            "99+".to_string()
+        } else {
+            unread.to_string()
+        };
+        let galley = ui
+            .painter()
+            .layout_no_wrap(text, FontId::proportional(10.0), Color32::WHITE);
+        let badge = Rect::from_center_size(
+            response.rect.right_top() + vec2(-2.0, 4.0),
+            vec2((galley.size().x + 6.0).max(14.0), 14.0),
+        );
+        ui.painter()
+            .rect_filled(badge, CornerRadius::same(7), Color32::from_rgb(200, 40, 40));
+        ui.painter()
+            .galley(badge.center() - galley.size() / 2.0, galley, Color32::WHITE);
+        response.on_hover_text(format!("{unread} unread notifications"))
+    }
+}
@@ -7,17 +7,92 @@
 //! ## Usage
 //!
 //! The [`Toast`] struct allows you to configure the appearance, message, color, margins, corner radius, width, and duration of the toast.
+//! Toasts share the [`AlertLevel`] severities and palette with alerts. Use [`Toast::with_level`] or the
+//! [`Toast::success`], [`Toast::info`], [`Toast::warning`] and [`Toast::error`] shorthands. Each level
+//! also comes with its own default duration, so errors stay on screen longer than successes.
 //! You can use the [`toast`] convenience function for a quick way to create a toast with a message.
+//! To post toasts from anywhere in an application and have them laid out and expired for you,
+//! use the [`ToastManager`](crate::ToastManager).
 //!
-//! **Important:**  
-//! Toasts rely on timeouts to disappear after a set duration. To ensure that the toast expires and disappears at the correct time,
-//! you should call `ctx.request_repaint_after(std::time::Duration::from_secs(1));` (or a similar interval) in your egui update loop.
-//! This ensures that egui continues to repaint even if there is no user interaction, allowing the toast to update and expire as expected.
+//! Toasts schedule their own repaint for the moment they expire, so they disappear on time even when
+//! there is no user input. Idle applications are not woken up any more often than that.
+//!
+//! A toast's timer starts the first time it is shown, not when the struct is built. Time is read from a
+//! [`ToastClock`], which defaults to egui's input time ([`EguiClock`]). A [`ManualClock`] can be
+//! injected to drive the timer deterministically, for example in tests.
+//!
+//! Clones of a toast share its timer, so a toast kept in application state and added by value each
+//! frame (`ui.add(toast.clone())`) still expires, and [`Toast::has_expired`] on the kept toast reports
+//! it. A toast built anew every frame starts over every frame and never expires. Other state, such as a
+//! dismissal or a swipe in progress, lives in the value itself, so interactive toasts should be added by
+//! mutable reference (`ui.add(&mut toast)`). Toasts posted to the [`ToastManager`](crate::ToastManager)
+//! get a timer of their own.
+//!
+//! The countdown freezes while the pointer hovers the toast or the toast holds keyboard focus, and resumes
+//! afterwards, so a long message does not vanish while it is being read. This can be turned off per toast
+//! with [`Toast::pause_on_hover`].
+//!
+//! Besides the message, a toast can show a bold title ([`Toast::title`]) and a leading icon. Toasts with a
+//! level get the level's icon, which can be replaced with [`Toast::icon`]. For anything beyond text,
+//! [`Toast::custom`] renders the body with a closure, so it can hold links, images or any other widget
+//! inside the usual toast frame. Custom toasts are not deduplicated.
+//!
+//! Toasts that must stay until someone acts on them, such as "Update available", can be made persistent
+//! with [`Toast::persistent`]. A persistent toast has no duration, never expires on its own and stays
+//! until it is dismissed. It shows no progress bar and schedules no repaint for an expiry.
+//!
+//! An optional progress bar along the bottom edge of the toast shows the time left, see
+//! [`Toast::progress_bar`]. While it is visible the toast repaints every frame to animate the bar;
+//! without it only the expiry itself triggers a repaint.
+//!
+//! Toasts can be dismissed early with an optional close button ([`Toast::can_close`]), by clicking
+//! anywhere on them ([`Toast::dismiss_on_click`]) or by swiping them sideways. A toast dragged past a
+//! third of its width flies out when released; below that it springs back. Swiping can be turned off
+//! with [`Toast::swipe_to_dismiss`]. The [`ToastManager`](crate::ToastManager) removes
+//! dismissed toasts; a standalone toast reports the dismissal through the [`ToastResponse`] returned by
+//! [`Toast::show`].
+//!
+//! Toasts describe themselves to assistive technology with their title and message, and the close
+//! button is labelled "Close notification". With the `accesskit` feature each toast is also exposed as a
+//! live region, so screen readers announce it when it appears: politely for success and info,
+//! assertively for warnings and errors.
+//!
+//! A toast can take keyboard focus like any other widget. While it has focus, Enter triggers its first
+//! action and Escape dismisses it. The [`ToastManager`](crate::ToastManager) adds shortcuts to move
+//! focus into and within the stack.
+//!
+//! Toasts can carry action buttons such as "Undo" ([`Toast::with_action`]). Each action has a stable
+//! identifier next to its visible label, so the label can be translated or repeated without changing how
+//! the application tells actions apart. The identifier of the clicked action is reported in the frame it
+//! happens, through [`ToastResponse::action`] or the
+//! [`ToastEvent`](crate::ToastEvent)s of the manager. Clicking an action dismisses the toast unless the
+//! action is configured with [`ToastAction::keep_open`].
+//!
+//! When the same message is posted over and over, the [`ToastManager`](crate::ToastManager) coalesces
+//! it into a single toast with a repeat counter ("×12") whose timer restarts on every repeat. Toasts are
+//! matched by their dedupe key, which defaults to the title, icon, message and level and can be set
+//! with [`Toast::dedupe_key`] or turned off with [`Toast::dedupe`].
+//!
+//! Related toasts can share a group key ([`Toast::group`]). The [`ToastManager`](crate::ToastManager)
+//! collapses the toasts of a group into a single summary with a count, which expands to list them. The
+//! summary reads the count followed by the group label ([`Toast::group_label`]), such as "20 files
+//! uploaded".
+//!
+//! A toast can show a spinner ([`Toast::spinner`]) while some work is in progress; its countdown only
+//! starts once the spinner is turned off. Managed toasts can be changed in place through their
+//! [`ToastId`](crate::ToastId), for example to turn "Uploading…" into "Uploaded" with
+//! [`ToastManager::update`](crate::ToastManager::update).
+//!
+//! A promise toast ([`Toast::promise`]) shows a spinner and a pending message until a background result
+//! arrives through a [`ToastPromise`], which wraps a `std::sync::mpsc::Receiver`, a `Future` or a polled
+//! closure. It then switches to a success or error toast with the resulting message. The toast does not
+//! expire while the result is pending. A future wakes the UI when it completes, and the other promises are
+//! polled on a short interval, so the result shows up even while no input arrives.
 //!
 //! ## Example
 //! ```
 //! # egui::__run_test_ui(|ui| {
-//! use egui_widget_ext::Toast;
+//! use egui_widget_ext::{Toast, ToastAction};
 //! use egui::Color32;
 //! use std::time::Duration;
 //!
@@ -28,31 +103,198 @@
 //!     .outer_margin(8)
 //!     .corner_radius(12)
 //!     .width(300.0)
-//!     .duration(Duration::from_secs(5));
+//!     .duration(Duration::from_secs(5))
+//!     .pause_on_hover(false)
+//!     .progress_bar(true);
 //! ui.add(custom_toast);
+//!
+//! // Showing a standalone toast and handling its dismissal
+//! let mut closeable = Toast::info("Click to dismiss")
+//!     .can_close(true)
+//!     .dismiss_on_click(true);
+//! if closeable.show(ui).dismissed {
+//!     println!("Toast dismissed!");
+//! }
+//!
+//! // Reacting to an action button
+//! let mut undo = Toast::info("Message deleted").with_action(ToastAction::new("undo", "Undo"));
+//! if undo.show(ui).action.as_deref() == Some("undo") {
+//!     println!("Restoring message");
+//! }
+//!
+//! // Using the severity shorthands
+//! ui.add(Toast::success("Saved"));
+//! ui.add(Toast::error("Upload failed").duration(Duration::from_secs(20)));
+//!
+//! // Keeping a toast until it is dismissed
+//! ui.add(Toast::info("Update available").persistent().can_close(true));
+//!
+//! // Adding a title, a custom icon and custom content
+//! ui.add(Toast::warning("Disk almost full").title("Storage").icon("💾"));
+//! ui.add(Toast::custom(|ui| {
+//!     ui.label("Release notes are available.");
+//!     ui.hyperlink_to("Read them", "https://example.com/notes");
+//! }));
+//! # });
+//! ```
+//!
+//! Expiry can be checked without sleeping by advancing the timer by hand:
+//! ```
+//! use egui_widget_ext::Toast;
+//! use std::time::Duration;
+//!
+//! let mut toast = Toast::new("Saved").duration(Duration::from_secs(2));
+//! toast.tick(10.0); // first shown at t = 10s
+//! assert!(!toast.has_expired());
+//! toast.tick(12.5);
+//! assert!(toast.has_expired());
+//! ```
+//!
+//! Waiting for a result computed on another thread:
+//! ```
+//! # egui::__run_test_ui(|ui| {
+//! use egui_widget_ext::{Toast, ToastManager, ToastPromise};
+//! use std::sync::mpsc;
+//!
+//! let (sender, receiver) = mpsc::channel();
+//! std::thread::spawn(move || {
+//!     let _ = sender.send(Ok("Report exported".to_string()));
+//! });
+//! ToastManager::add(
+//!     ui.ctx(),
+//!     Toast::promise("Exporting report…", ToastPromise::from_receiver(receiver)),
+//! );
 //! # });
 //! ```
 //!
 //! ## Components
 //! - [`Toast`]: Struct for configuring and displaying the toast widget.
 //! - [`toast`]: Convenience function for creating a toast widget.
+//! - [`ToastAction`]: Struct for configuring an action button on a toast.
+//! - [`ToastResponse`]: Struct returned by [`Toast::show`].
+//! - [`ToastPromise`]: Struct for a pending result a promise toast waits for.
+//! - [`ToastClock`]: Trait for the time source used to expire toasts.
+//! - [`EguiClock`]: Default clock reading egui's input time.
+//! - [`ManualClock`]: Manually driven clock for deterministic timing.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{self, Poll, Wake, Waker};
+use std::time::Duration;
+
+use egui::{
+    Align, Button, Color32, Context, CornerRadius, Frame, Key, Label, Layout, Margin,
+    PointerButton, Rect, Response, RichText, Sense, Spinner, Stroke, Ui, UiBuilder, ViewportId,
+    Widget, WidgetInfo, WidgetType, pos2, vec2,
+};
+
+use crate::{Alert, AlertLevel};
+
+/// Fraction of its width a toast must be dragged past to be dismissed when released.
+const SWIPE_THRESHOLD: f32 = 1.0 / 3.0;
+/// Rate per second at which a released toast closes the gap to its resting or fly-out position.
+const SWIPE_SPEED: f32 = 15.0;
+/// Interval at which a pending promise is polled while nothing else wakes the UI.
+const PROMISE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Source of time used to measure how long a [`Toast`] has been shown.
+///
+/// Readings are in seconds. Only the difference between readings matters, so the origin is arbitrary.
+pub trait ToastClock: std::fmt::Debug + Send + Sync {
+    /// Current time in seconds.
+    fn now(&self, ctx: &Context) -> f64;
+}
 
-use std::time::{Duration, Instant};
+/// Clock reading egui's input time (`ctx.input(|i| i.time)`). This is the default toast clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EguiClock;
 
-use egui::{Color32, CornerRadius, Frame, Label, Margin, Response, RichText, Stroke, Ui, Widget};
+impl ToastClock for EguiClock {
+    fn now(&self, ctx: &Context) -> f64 {
+        ctx.input(|i| i.time)
+    }
+}
+
+/// Manually driven clock, mainly intended for tests.
+///
+/// Clones share the same reading, so a test can keep one clone and hand another to a toast.
+///
+/// # Example
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui_widget_ext::{ManualClock, Toast};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::default();
+/// let mut toast = Toast::new("Saved")
+///     .duration(Duration::from_secs(2))
+///     .with_clock(clock.clone());
+/// ui.add(&mut toast); // the timer starts here
+/// clock.advance(Duration::from_secs(3));
+/// ui.add(&mut toast);
+/// assert!(toast.has_expired());
+/// # });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    /// Current reading in seconds, stored as the bits of an `f64`.
+    seconds: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    /// Create a clock with the given reading in seconds.
+    pub fn new(seconds: f64) -> Self {
+        Self {
+            seconds: Arc::new(AtomicU64::new(seconds.to_bits())),
+        }
+    }
+
+    /// Set the clock reading in seconds.
+    pub fn set(&self, seconds: f64) {
+        self.seconds.store(seconds.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Move the clock forward by the given duration.
+    pub fn advance(&self, by: Duration) {
+        let _ = self
+            .seconds
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                Some((f64::from_bits(bits) + by.as_secs_f64()).to_bits())
+            });
+    }
+
+    /// Current clock reading in seconds.
+    pub fn seconds(&self) -> f64 {
+        f64::from_bits(self.seconds.load(Ordering::Relaxed))
+    }
+}
+
+impl ToastClock for ManualClock {
+    fn now(&self, _ctx: &Context) -> f64 {
+        self.seconds()
+    }
+}
 
 /// A customizable toast notification widget for egui.
 ///
 /// The `Toast` struct allows you to configure the appearance and message of the toast box.
 /// It supports setting the background color, message, inner and outer margins, corner radius, width,
 /// and the duration for which the toast should be visible. Toasts are intended to be temporary and
-/// will expire after the specified duration.
+/// will expire after the specified duration, unless they are made persistent.
 #[derive(Debug, Clone)]
 pub struct Toast {
+    /// The bold title shown above the message, if any.
+    pub title: Option<String>,
     /// The message to display in the toast.
     pub message: String,
     /// The background color of the toast.
     pub color: Color32,
+    /// The severity level of the toast, if one was set.
+    pub level: Option<AlertLevel>,
+    /// The icon shown in front of the message, if any. Set from the level unless replaced.
+    pub icon: Option<String>,
     /// The inner margin (padding) of the toast box.
     pub inner_margin: i8,
     /// The outer margin of the toast box.
@@ -61,23 +303,87 @@ pub struct Toast {
     pub corner_radius: u8,
     /// Toast width, if specified.
     pub width: Option<f32>,
-    /// Start instant for the toast, used for timing.
-    pub start_instant: Instant,
-    /// Duration for which the toast should be visible.
-    pub duration: Duration,
+    /// Duration for which the toast should be visible, `None` for a persistent toast that stays until
+    /// it is dismissed.
+    pub duration: Option<Duration>,
+    /// Whether the countdown freezes while the toast is hovered or focused.
+    pub pause_on_hover: bool,
+    /// Whether a bar showing the time left is drawn along the bottom edge.
+    pub show_progress: bool,
+    /// Whether to show the close ("X") button.
+    pub can_close: bool,
+    /// Whether clicking anywhere on the toast dismisses it.
+    pub dismiss_on_click: bool,
+    /// Whether dragging the toast sideways past a threshold dismisses it.
+    pub swipe_to_dismiss: bool,
+    /// Action buttons shown on the toast, in order.
+    pub actions: Vec<ToastAction>,
+    /// Whether a spinner is shown in front of the message. The countdown is held while it is shown.
+    pub spinner: bool,
+    /// Pending result the toast waits for, see [`Toast::promise`].
+    pub promise: Option<ToastPromise>,
+    /// Whether the toast is coalesced with managed toasts that have the same dedupe key.
+    pub dedupe: bool,
+    /// Key used to recognize repeats of the toast, its title, icon, message and level if unset.
+    pub dedupe_key: Option<String>,
+    /// Key of the group the toast is collapsed into with other managed toasts, if any.
+    pub group: Option<String>,
+    /// Text following the count in the summary of the group, the group key if unset.
+    pub group_label: Option<String>,
+    /// Viewport a managed toast is shown in, the focused viewport if unset.
+    pub viewport: Option<ViewportId>,
+    /// Closure rendering the body in place of the message, see [`Toast::custom`].
+    content: Option<ToastContent>,
+    /// Whether the duration was set explicitly rather than taken from the level.
+    custom_duration: bool,
+    /// Clock used to time the toast, egui's input time if unset.
+    pub(crate) clock: Option<Arc<dyn ToastClock>>,
+    /// Countdown state, shared with the clones of the toast.
+    timer: Arc<Mutex<ToastTimer>>,
+    /// Whether the toast was dismissed before expiring.
+    dismissed: bool,
+    /// Number of times the toast was posted, including coalesced repeats.
+    repeat_count: u32,
+    /// Horizontal distance the toast is shifted by a swipe.
+    swipe_offset: f32,
+    /// Whether the toast was released past the swipe threshold and is flying out.
+    swiped: bool,
 }
 
 impl Default for Toast {
     fn default() -> Self {
         Self {
+            title: None,
             message: "No message provided".to_string(),
             color: Color32::from_rgb(200, 200, 255), // Default to a blue color
+            level: None,
+            icon: None,
             inner_margin: 10,
             outer_margin: 10,
             corner_radius: 4,
-            width: None,                      // Default to no specific width
-            start_instant: Instant::now(),    // Start timing immediately
-            duration: Duration::from_secs(3), // Default duration of 3 seconds
+            width: None,                            // Default to no specific width
+            duration: Some(Duration::from_secs(3)), // Default duration of 3 seconds
+            pause_on_hover: true, // Default to freezing the countdown while hovered
+            show_progress: false, // Default to no progress bar
+            can_close: false,     // Default to no close button
+            dismiss_on_click: false,
+            swipe_to_dismiss: true,
+            actions: Vec::new(),
+            spinner: false,
+            promise: None,
+            dedupe: true,
+            dedupe_key: None, // Default to matching on title, icon, message and level
+            group: None,
+            group_label: None, // Default to the group key
+            viewport: None,    // Default to the focused viewport
+            content: None,
+            custom_duration: false,
+            clock: None,           // Default to egui's input time
+            timer: Arc::default(), // Timing starts when the toast is first shown
+            dismissed: false,
+            repeat_count: 1,
+            swipe_offset: 0.0,
+            swiped: false,
         }
     }
 }
@@ -93,6 +399,80 @@ impl Toast {
         }
     }
 
+    /// Create a new success toast with the given message.
+    pub fn success(message: &str) -> Self {
+        Self::new(message).with_level(AlertLevel::Success)
+    }
+
+    /// Create a new info toast with the given message.
+    pub fn info(message: &str) -> Self {
+        Self::new(message).with_level(AlertLevel::Info)
+    }
+
+    /// Create a new warning toast with the given message.
+    pub fn warning(message: &str) -> Self {
+        Self::new(message).with_level(AlertLevel::Warning)
+    }
+
+    /// Create a new error toast with the given message.
+    pub fn error(message: &str) -> Self {
+        Self::new(message).with_level(AlertLevel::Error)
+    }
+
+    /// Create a new promise toast showing a spinner and the given pending message.
+    ///
+    /// Once the promise resolves, the toast becomes a success toast with the `Ok` message or an error
+    /// toast with the `Err` message, and its countdown starts. Promise toasts are not deduplicated.
+    pub fn promise(message: &str, promise: ToastPromise) -> Self {
+        let mut toast = Self::info(message).spinner(true).dedupe(false);
+        toast.promise = Some(promise);
+        toast
+    }
+
+    /// Create a new toast whose body is rendered by the given closure instead of a message.
+    ///
+    /// The closure runs inside the toast frame every time the toast is shown, with black text to match
+    /// the light toast colors. A [`Toast::message`] is not shown but still describes the toast, for
+    /// example in the history of the [`ToastManager`](crate::ToastManager). Custom toasts are not
+    /// deduplicated.
+    pub fn custom(content: impl Fn(&mut Ui) + Send + Sync + 'static) -> Self {
+        let mut toast = Self::new("").dedupe(false);
+        toast.content = Some(ToastContent(Arc::new(content)));
+        toast
+    }
+
+    /// Set the toast's severity level, which determines its background color, icon and default
+    /// duration.
+    ///
+    /// A duration set explicitly with [`Toast::duration`] is kept.
+    pub fn with_level(mut self, level: AlertLevel) -> Self {
+        self.level = Some(level);
+        self.color = Alert::level_to_color(level);
+        self.icon = Some(Self::level_to_icon(level).to_string());
+        if !self.custom_duration {
+            self.duration = Some(Self::level_to_duration(level));
+        }
+        self
+    }
+
+    /// Set the message displayed in the toast.
+    pub fn message(mut self, message: &str) -> Self {
+        self.message = message.to_string();
+        self
+    }
+
+    /// Set the bold title shown above the message.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Set the icon shown in front of the message, such as an emoji, replacing the level's icon.
+    pub fn icon(mut self, icon: &str) -> Self {
+        self.icon = Some(icon.to_string());
+        self
+    }
+
     /// Set the background color of the toast.
     pub fn with_color(mut self, color: Color32) -> Self {
         self.color = color;
@@ -125,20 +505,410 @@ impl Toast {
 
     /// Set the duration for which the toast should be visible.
     pub fn duration(mut self, duration: Duration) -> Self {
-        self.duration = duration;
+        self.duration = Some(duration);
+        self.custom_duration = true;
         self
     }
 
-    /// Check if the toast has expired based on the current time.
+    /// Make the toast persistent, so it never expires on its own and stays until it is dismissed.
+    ///
+    /// Give a persistent toast a way out, such as a close button or an action.
+    pub fn persistent(mut self) -> Self {
+        self.duration = None;
+        self.custom_duration = true;
+        self
+    }
+
+    /// Set whether the countdown freezes while the toast is hovered or focused.
+    pub fn pause_on_hover(mut self, pause: bool) -> Self {
+        self.pause_on_hover = pause;
+        self
+    }
+
+    /// Set whether a bar showing the time left is drawn along the bottom edge of the toast.
+    ///
+    /// The bar uses a darker shade of the toast color.
+    pub fn progress_bar(mut self, show: bool) -> Self {
+        self.show_progress = show;
+        self
+    }
+
+    /// Set whether the close ("X") button is shown.
+    pub fn can_close(mut self, closeable: bool) -> Self {
+        self.can_close = closeable;
+        self
+    }
+
+    /// Set whether clicking anywhere on the toast dismisses it.
+    pub fn dismiss_on_click(mut self, dismiss: bool) -> Self {
+        self.dismiss_on_click = dismiss;
+        self
+    }
+
+    /// Set whether dragging the toast sideways past a threshold dismisses it.
+    pub fn swipe_to_dismiss(mut self, swipe: bool) -> Self {
+        self.swipe_to_dismiss = swipe;
+        self
+    }
+
+    /// Add an action button to the toast. Actions are shown in the order they are added.
+    pub fn with_action(mut self, action: ToastAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Set whether a spinner is shown in front of the message.
+    ///
+    /// The countdown is held while the spinner is shown, so a loading toast does not expire before the
+    /// work it describes is done.
+    pub fn spinner(mut self, spinner: bool) -> Self {
+        self.spinner = spinner;
+        self
+    }
+
+    /// Set whether the toast is coalesced with managed toasts that have the same dedupe key.
+    pub fn dedupe(mut self, dedupe: bool) -> Self {
+        self.dedupe = dedupe;
+        self
+    }
+
+    /// Set the key used to recognize repeats of the toast instead of its title, icon, message and level.
+    pub fn dedupe_key(mut self, key: &str) -> Self {
+        self.dedupe_key = Some(key.to_string());
+        self
+    }
+
+    /// Set the group key of the toast.
+    ///
+    /// Managed toasts sharing a group key are collapsed into one summary with a count, that lists its
+    /// members when clicked. The key only identifies the group; set the text shown next to the count
+    /// with [`Toast::group_label`].
+    pub fn group(mut self, key: &str) -> Self {
+        self.group = Some(key.to_string());
+        self
+    }
+
+    /// Set the text following the count in the summary of the group, for example "files uploaded" to
+    /// read "20 files uploaded".
+    ///
+    /// The label of the most recently posted toast of the group that has one is used, and the group key
+    /// if none has.
+    pub fn group_label(mut self, label: &str) -> Self {
+        self.group_label = Some(label.to_string());
+        self
+    }
+
+    /// Set the viewport a managed toast is shown in, instead of the one that has focus.
+    pub fn viewport(mut self, viewport: ViewportId) -> Self {
+        self.viewport = Some(viewport);
+        self
+    }
+
+    /// Set the clock used to time the toast instead of egui's input time.
+    pub fn with_clock(mut self, clock: impl ToastClock + 'static) -> Self {
+        self.clock = Some(Arc::new(clock));
+        self
+    }
+
+    /// Advance the toast timer to the given clock reading in seconds.
+    ///
+    /// The timer starts on the first call, which normally happens the first time the toast is shown.
+    /// Time that passes while the countdown is paused or the spinner is shown is not counted.
+    pub fn tick(&mut self, now: f64) {
+        let spinner = self.spinner;
+        let mut timer = self.timer();
+        if let Some(last) = timer.last_tick.filter(|_| !timer.paused && !spinner) {
+            timer.elapsed += Duration::from_secs_f64((now - last).max(0.0));
+        }
+        timer.last_tick = Some(now);
+    }
+
+    /// Restart the countdown, giving the toast its whole duration again.
+    pub fn restart(&mut self) {
+        self.timer().elapsed = Duration::ZERO;
+    }
+
+    /// Lock the countdown state.
+    fn timer(&self) -> MutexGuard<'_, ToastTimer> {
+        self.timer.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Give the toast a countdown of its own, no longer shared with the toast it was cloned from.
+    pub(crate) fn detach_timer(&mut self) {
+        let timer = self.timer().clone();
+        self.timer = Arc::new(Mutex::new(timer));
+    }
+
+    /// Freeze or resume the countdown from the next tick.
+    fn set_paused(&self, paused: bool) {
+        self.timer().paused = paused;
+    }
+
+    /// Dismiss the toast before it expires.
+    pub fn dismiss(&mut self) {
+        self.dismissed = true;
+    }
+
+    /// Check if the toast was dismissed, through its close button, by a click or by [`Toast::dismiss`].
+    pub fn is_dismissed(&self) -> bool {
+        self.dismissed
+    }
+
+    /// Number of times the toast was posted, including repeats coalesced into it.
+    pub fn repeat_count(&self) -> u32 {
+        self.repeat_count
+    }
+
+    /// Key used to match repeats of the toast, `None` if deduplication is turned off.
+    pub(crate) fn coalesce_key(&self) -> Option<String> {
+        if !self.dedupe {
+            return None;
+        }
+        Some(match &self.dedupe_key {
+            Some(key) => key.clone(),
+            None => format!(
+                "{:?}:{:?}:{:?}:{}",
+                self.level, self.title, self.icon, self.message
+            ),
+        })
+    }
+
+    /// Take over the content of a newer repeat of the toast, restarting the timer.
+    ///
+    /// The repeat count accumulates and the pause state is kept, as the toast stays where it is.
+    pub(crate) fn coalesce(&mut self, newer: Toast) {
+        let timer = ToastTimer {
+            elapsed: Duration::ZERO,
+            ..self.timer().clone()
+        };
+        *self = Toast {
+            repeat_count: self.repeat_count + newer.repeat_count,
+            timer: Arc::new(Mutex::new(timer)),
+            viewport: self.viewport,
+            ..newer
+        };
+    }
+
+    /// Check if the countdown is currently frozen because the toast is hovered or focused.
+    pub fn is_paused(&self) -> bool {
+        self.timer().paused
+    }
+
+    /// Check if the toast has no duration and stays until it is dismissed.
+    pub fn is_persistent(&self) -> bool {
+        self.duration.is_none()
+    }
+
+    /// Check if the toast has been shown for its whole duration.
     ///
-    /// Returns `true` if the toast's duration has elapsed, otherwise `false`.
+    /// Returns `true` if the toast's duration has elapsed, otherwise `false`. Persistent toasts never
+    /// expire.
     pub fn has_expired(&self) -> bool {
-        self.start_instant.elapsed() >= self.duration
+        self.duration
+            .is_some_and(|duration| self.timer().elapsed >= duration)
+    }
+
+    /// Time left before the toast expires.
+    ///
+    /// Returns [`Duration::ZERO`] once the toast has expired, and `None` for a persistent toast.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.duration
+            .map(|duration| duration.saturating_sub(self.timer().elapsed))
+    }
+
+    /// Fraction of the duration that is left, from `1.0` when first shown down to `0.0` on expiry.
+    ///
+    /// Persistent toasts always have `1.0` left.
+    pub fn remaining_fraction(&self) -> f32 {
+        match (self.duration, self.remaining()) {
+            (Some(duration), Some(remaining)) if !duration.is_zero() => {
+                (remaining.as_secs_f32() / duration.as_secs_f32()).clamp(0.0, 1.0)
+            }
+            (Some(_), _) => 0.0,
+            (None, _) => 1.0,
+        }
+    }
+
+    /// Map an [`AlertLevel`] to the icon of a toast with that level.
+    fn level_to_icon(level: AlertLevel) -> &'static str {
+        match level {
+            AlertLevel::Success => "✔",
+            AlertLevel::Info => "ℹ",
+            AlertLevel::Warning => "⚠",
+            AlertLevel::Error => "❌",
+        }
+    }
+
+    /// Map an [`AlertLevel`] to the default duration of a toast with that level.
+    fn level_to_duration(level: AlertLevel) -> Duration {
+        match level {
+            AlertLevel::Success => Duration::from_secs(3),
+            AlertLevel::Info => Duration::from_secs(4),
+            AlertLevel::Warning => Duration::from_secs(6),
+            AlertLevel::Error => Duration::from_secs(10),
+        }
+    }
+
+    /// Darker shade of the toast color used for the progress bar.
+    fn progress_color(&self) -> Color32 {
+        let darken = |channel: u8| (channel as f32 * 0.6) as u8;
+        Color32::from_rgb(
+            darken(self.color.r()),
+            darken(self.color.g()),
+            darken(self.color.b()),
+        )
     }
 }
 
-impl Widget for Toast {
-    fn ui(self, ui: &mut Ui) -> Response {
+/// Countdown state of a [`Toast`].
+#[derive(Debug, Clone, Default)]
+struct ToastTimer {
+    /// Time the toast has been shown for.
+    elapsed: Duration,
+    /// Clock reading of the last time the toast was shown, `None` until it is first shown.
+    last_tick: Option<f64>,
+    /// Whether the countdown is frozen, updated every time the toast is shown.
+    paused: bool,
+}
+
+/// Closure rendering the body of a custom toast, see [`Toast::custom`].
+#[derive(Clone)]
+struct ToastContent(Arc<dyn Fn(&mut Ui) + Send + Sync>);
+
+impl std::fmt::Debug for ToastContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ToastContent").finish_non_exhaustive()
+    }
+}
+
+/// Polling function of a [`ToastPromise`], returning `None` while the result is pending.
+type PromisePoll = dyn FnMut(&Context) -> Option<Result<String, String>> + Send;
+
+/// A pending result that a promise toast waits for, see [`Toast::promise`].
+///
+/// The result is the message to show: `Ok` for a success toast, `Err` for an error toast.
+#[derive(Clone)]
+pub struct ToastPromise {
+    /// Polls the result, returning `None` while it is pending.
+    poll: Arc<Mutex<PromisePoll>>,
+}
+
+impl std::fmt::Debug for ToastPromise {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToastPromise").finish_non_exhaustive()
+    }
+}
+
+impl ToastPromise {
+    /// Wait for the first message sent through a channel.
+    ///
+    /// If the sender is dropped without sending, the toast reports an error.
+    pub fn from_receiver(receiver: Receiver<Result<String, String>>) -> Self {
+        Self::from_fn(move || match receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err("The operation was abandoned".to_string())),
+        })
+    }
+
+    /// Wait for a future to complete.
+    ///
+    /// The future is polled when the toast is shown and wakes the UI with a repaint when it can make
+    /// progress, so no executor is needed.
+    pub fn from_future(
+        future: impl Future<Output = Result<String, String>> + Send + 'static,
+    ) -> Self {
+        let mut future = Box::pin(future);
+        Self::new(move |ctx| {
+            let waker = Waker::from(Arc::new(RepaintWaker(ctx.clone())));
+            match future.as_mut().poll(&mut task::Context::from_waker(&waker)) {
+                Poll::Ready(result) => Some(result),
+                Poll::Pending => None,
+            }
+        })
+    }
+
+    /// Wait for a closure to return a result. The closure is called every frame the toast is shown, and at
+    /// least every 100 milliseconds while the result is pending.
+    pub fn from_fn(
+        mut poll: impl FnMut() -> Option<Result<String, String>> + Send + 'static,
+    ) -> Self {
+        Self::new(move |_ctx| poll())
+    }
+
+    /// Create a promise from a polling function taking the egui context.
+    fn new(poll: impl FnMut(&Context) -> Option<Result<String, String>> + Send + 'static) -> Self {
+        Self {
+            poll: Arc::new(Mutex::new(poll)),
+        }
+    }
+
+    /// Poll the result, returning `None` while it is pending.
+    fn poll(&self, ctx: &Context) -> Option<Result<String, String>> {
+        let mut guard = self.poll.lock().unwrap_or_else(PoisonError::into_inner);
+        let poll = &mut *guard;
+        poll(ctx)
+    }
+}
+
+/// Waker requesting a repaint, so a completed future is picked up on the next frame.
+struct RepaintWaker(Context);
+
+impl Wake for RepaintWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.request_repaint();
+    }
+}
+
+/// A labelled button shown on a [`Toast`], such as "Undo", "Retry" or "View".
+///
+/// Clicking an action dismisses the toast unless it is configured with [`ToastAction::keep_open`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToastAction {
+    /// Identifier reported when the action is clicked, independent of the label.
+    pub id: String,
+    /// The text of the action button.
+    pub label: String,
+    /// Whether the toast stays open after the action is clicked.
+    pub keep_open: bool,
+}
+
+impl ToastAction {
+    /// Create a new action with the given identifier and label that dismisses the toast when clicked.
+    pub fn new(id: &str, label: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            label: label.to_string(),
+            keep_open: false,
+        }
+    }
+
+    /// Set whether the toast stays open after the action is clicked.
+    pub fn keep_open(mut self, keep_open: bool) -> Self {
+        self.keep_open = keep_open;
+        self
+    }
+}
+
+/// The result of showing a [`Toast`] with [`Toast::show`].
+#[derive(Debug, Clone)]
+pub struct ToastResponse {
+    /// The response covering the whole toast.
+    pub response: Response,
+    /// Whether the toast was dismissed this frame, through its close button, an action or by clicking it.
+    pub dismissed: bool,
+    /// Identifier of the action button clicked this frame, if any.
+    pub action: Option<String>,
+}
+
+impl Toast {
+    /// Render the toast, advance its timer and report whether it was dismissed.
+    ///
+    /// Use this instead of `ui.add(&mut toast)` when the toast is not owned by the
+    /// [`ToastManager`](crate::ToastManager) and the dismissal needs to be handled by the caller.
+    pub fn show(&mut self, ui: &mut Ui) -> ToastResponse {
+        self.advance(ui.ctx());
+
         let frame = Frame::default()
             .fill(self.color)
             .stroke(Stroke::new(1.0, Color32::from_rgb(200, 200, 200)))
@@ -146,20 +916,445 @@ impl Widget for Toast {
             .inner_margin(Margin::same(self.inner_margin))
             .outer_margin(Margin::same(self.outer_margin));
 
-        let response = frame
-            .show(ui, |ui| {
+        // The child ui senses clicks and drags behind the toast contents, so the close button still
+        // gets its own.
+        // The toast itself can take keyboard focus, so it can be reached and handled from the keyboard.
+        let mut sense = if self.dismiss_on_click {
+            Sense::click()
+        } else {
+            Sense::focusable_noninteractive()
+        };
+        if self.swipe_to_dismiss {
+            sense = sense.union(Sense::drag());
+        }
+        // The toast is drawn shifted by the swipe, while the space it takes up in the parent stays put.
+        let shift = vec2(self.swipe_offset, 0.0);
+        let mut child = ui.new_child(
+            UiBuilder::new()
+                .max_rect(ui.available_rect_before_wrap().translate(shift))
+                .layout(*ui.layout())
+                .sense(sense),
+        );
+        let fade = self.swipe_offset.abs() / child.max_rect().width().max(1.0);
+        child.multiply_opacity(1.0 - fade.min(1.0));
+        let mut controls = frame
+            .show(&mut child, |ui| {
                 if let Some(width) = self.width {
                     ui.set_width(width);
                 }
-                ui.horizontal(|ui| {
-                    let r1 = ui
-                        .add(Label::new(RichText::new(&self.message).color(Color32::BLACK)).wrap());
-                    ui.add_space(ui.available_width());
-                    r1
-                })
-                .inner
+                self.contents_ui(ui)
             })
             .inner;
-        response
+        let rect = child.min_rect();
+        let response = child.response();
+        ui.advance_cursor_after_rect(rect.translate(-shift));
+
+        response.widget_info(|| {
+            WidgetInfo::labeled(WidgetType::Other, ui.is_enabled(), self.accessible_label())
+        });
+        #[cfg(feature = "accesskit")]
+        Alert::announce(ui.ctx(), response.id, self.level);
+
+        // Enter on the focused toast triggers the primary action. egui releases focus on Escape before
+        // the toast is shown, so losing focus to it counts as well.
+        let (enter, escape) = ui.input(|input| {
+            (
+                input.key_pressed(Key::Enter),
+                input.key_pressed(Key::Escape),
+            )
+        });
+        if enter && response.has_focus() && !self.actions.is_empty() {
+            controls.action = Some(0);
+        }
+        let escaped = escape && (response.has_focus() || response.lost_focus());
+
+        let action = controls.action.map(|index| self.actions[index].id.clone());
+        let action_closes = controls
+            .action
+            .is_some_and(|index| !self.actions[index].keep_open);
+        let swiped = self.swipe(ui, &response, rect.width());
+        // Only pointer clicks dismiss, as egui also reports Enter on the focused toast as a click.
+        let dismissed = controls.close_clicked
+            || action_closes
+            || (self.dismiss_on_click && response.clicked_by(PointerButton::Primary))
+            || swiped
+            || escaped;
+        if dismissed {
+            self.dismiss();
+            ui.ctx().request_repaint();
+        }
+
+        if self.show_progress && !self.is_persistent() {
+            // Inset by the corner radius so the bar does not stick out of the rounded corners.
+            let rect = rect
+                .shrink(self.outer_margin as f32)
+                .shrink2(vec2(self.corner_radius as f32, 1.0));
+            let width = rect.width() * self.remaining_fraction();
+            let bar = Rect::from_min_max(
+                pos2(rect.left(), rect.bottom() - 3.0),
+                pos2(rect.left() + width, rect.bottom()),
+            );
+            child
+                .painter()
+                .rect_filled(bar, CornerRadius::ZERO, self.progress_color());
+        }
+
+        // The pause takes effect from the next tick, leaving and entering are both input events that
+        // trigger a repaint.
+        let hovered = response.contains_pointer();
+        let focused = response.has_focus() || controls.focused;
+        self.set_paused(self.pause_on_hover && (hovered || focused));
+
+        self.schedule_repaint(ui.ctx(), self.show_progress);
+
+        ToastResponse {
+            response,
+            dismissed,
+            action,
+        }
+    }
+
+    /// Follow the pointer while the toast is dragged, then move it back or out once released.
+    ///
+    /// Returns `true` in the frame the toast is released past the swipe threshold.
+    fn swipe(&mut self, ui: &Ui, response: &Response, width: f32) -> bool {
+        if !self.swipe_to_dismiss {
+            return false;
+        }
+        if response.dragged() && !self.swiped {
+            self.swipe_offset += response.drag_delta().x;
+            return false;
+        }
+
+        let released = response.drag_stopped()
+            && !self.swiped
+            && self.swipe_offset.abs() > width * SWIPE_THRESHOLD;
+        self.swiped |= released;
+        let target = if self.swiped {
+            self.swipe_offset.signum() * width
+        } else {
+            0.0
+        };
+        if self.swipe_offset != target {
+            let dt = ui.input(|input| input.stable_dt);
+            self.swipe_offset += (target - self.swipe_offset) * (1.0 - (-dt * SWIPE_SPEED).exp());
+            if (target - self.swipe_offset).abs() < 0.5 {
+                self.swipe_offset = target;
+            }
+            ui.ctx().request_repaint();
+        }
+        released
+    }
+
+    /// Advance a toast that is kept out of view, such as a member of a collapsed group.
+    ///
+    /// The countdown runs unless `hovered` pauses it, and a repaint is scheduled for the expiry.
+    pub(crate) fn show_hidden(&mut self, ctx: &Context, hovered: bool) {
+        self.advance(ctx);
+        self.set_paused(self.pause_on_hover && hovered);
+        self.schedule_repaint(ctx, false);
+    }
+
+    /// Pick up the result of the promise, if any, and advance the timer to the current time.
+    fn advance(&mut self, ctx: &Context) {
+        let result = self.promise.as_ref().and_then(|promise| promise.poll(ctx));
+        if let Some(result) = result {
+            self.resolve(result);
+        }
+
+        let now = match &self.clock {
+            Some(clock) => clock.now(ctx),
+            None => EguiClock.now(ctx),
+        };
+        self.tick(now);
+    }
+
+    /// Wake up when the toast expires, or every frame while `animate` is set.
+    ///
+    /// egui keeps the soonest of all requested repaints, so a stack of toasts results in a single
+    /// repaint at the next expiry. Persistent toasts have nothing to wake up for. A pending promise is
+    /// polled on an interval, as a channel or closure cannot wake the UI by itself.
+    fn schedule_repaint(&self, ctx: &Context, animate: bool) {
+        if self.promise.is_some() {
+            ctx.request_repaint_after(PROMISE_POLL_INTERVAL);
+            return;
+        }
+        if let Some(remaining) = self
+            .remaining()
+            .filter(|_| !self.is_paused() && !self.spinner && !self.dismissed)
+        {
+            if animate {
+                ctx.request_repaint();
+            } else {
+                ctx.request_repaint_after(remaining);
+            }
+        }
+    }
+
+    /// Switch a promise toast to the success or error toast for the given result.
+    fn resolve(&mut self, result: Result<String, String>) {
+        let (message, level) = match result {
+            Ok(message) => (message, AlertLevel::Success),
+            Err(message) => (message, AlertLevel::Error),
+        };
+        let mut toast = std::mem::take(self)
+            .message(&message)
+            .with_level(level)
+            .spinner(false);
+        toast.promise = None;
+        toast.restart();
+        *self = toast;
+    }
+
+    /// Render the icon, the body and the controls of the toast inside its frame.
+    fn contents_ui(&self, ui: &mut Ui) -> ToastControls {
+        let mut controls = ToastControls::default();
+        ui.horizontal(|ui| {
+            if self.spinner {
+                ui.add(Spinner::new().color(Color32::BLACK));
+            }
+            if let Some(icon) = &self.icon {
+                ui.add(
+                    Label::new(RichText::new(icon).color(Color32::BLACK).size(18.0))
+                        .selectable(false),
+                );
+            }
+            // The controls are laid out first from the right, the body takes the space left of them.
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                if self.can_close {
+                    let close_resp = ui.add(
+                        Button::new(RichText::new("X").color(Color32::DARK_RED).strong())
+                            .frame(false),
+                    );
+                    close_resp.widget_info(|| {
+                        WidgetInfo::labeled(
+                            WidgetType::Button,
+                            ui.is_enabled(),
+                            "Close notification",
+                        )
+                    });
+                    controls.close_clicked = close_resp.clicked();
+                    controls.focused |= close_resp.has_focus();
+                }
+                // Right to left, so add the actions in reverse to keep their order on screen.
+                for (index, action) in self.actions.iter().enumerate().rev() {
+                    let action_resp = ui.button(&action.label);
+                    if action_resp.clicked() {
+                        controls.action = Some(index);
+                    }
+                    controls.focused |= action_resp.has_focus();
+                }
+                ui.vertical(|ui| {
+                    controls.focused |= self.body_ui(ui);
+                });
+            });
+        });
+        controls
+    }
+
+    /// Text describing the toast to assistive technology: the title, if any, followed by the message.
+    fn accessible_label(&self) -> String {
+        match &self.title {
+            Some(title) => format!("{title}: {}", self.message),
+            None => self.message.clone(),
+        }
+    }
+
+    /// Render the title and the message or custom content of the toast.
+    ///
+    /// Returns whether a widget of the custom content holds keyboard focus.
+    fn body_ui(&self, ui: &mut Ui) -> bool {
+        // The text is not selectable, so dragging and clicking it swipes or dismisses the toast.
+        if let Some(title) = &self.title {
+            ui.add(
+                Label::new(RichText::new(title).color(Color32::BLACK).strong())
+                    .wrap()
+                    .selectable(false),
+            );
+        }
+        if let Some(content) = &self.content {
+            let scope = ui.scope(|ui| {
+                ui.visuals_mut().override_text_color = Some(Color32::BLACK);
+                (content.0)(ui);
+            });
+            // The custom widgets are unknown, so look for the focused widget inside the content.
+            let ctx = ui.ctx();
+            let content_focused = ctx
+                .memory(|memory| memory.focused())
+                .and_then(|id| ctx.read_response(id))
+                .is_some_and(|response| scope.response.rect.contains_rect(response.rect));
+            return content_focused;
+        }
+        let text = if self.repeat_count > 1 {
+            format!("{}  ×{}", self.message, self.repeat_count)
+        } else {
+            self.message.clone()
+        };
+        ui.add(
+            Label::new(RichText::new(text).color(Color32::BLACK))
+                .wrap()
+                .selectable(false),
+        );
+        false
+    }
+}
+
+/// Interactions with the controls inside a toast during one frame.
+#[derive(Debug, Default)]
+struct ToastControls {
+    /// Whether the close button was clicked.
+    close_clicked: bool,
+    /// Index of the action button that was clicked.
+    action: Option<usize>,
+    /// Whether any control holds keyboard focus.
+    focused: bool,
+}
+
+impl Widget for Toast {
+    /// Render the toast and advance its timer, which is shared with the clones of the toast.
+    ///
+    /// A dismissal is lost with the value. Add the toast by mutable reference to keep it.
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        self.show(ui).response
+    }
+}
+
+impl Widget for &mut Toast {
+    /// Render the toast and advance its timer.
+    ///
+    /// A dismissal is recorded on the toast and can be checked with [`Toast::is_dismissed`].
+    fn ui(self, ui: &mut Ui) -> Response {
+        self.show(ui).response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a toast of the given duration timed by a clock the test controls.
+    fn timed(seconds: u64) -> (Toast, ManualClock) {
+        let clock = ManualClock::default();
+        let toast = Toast::new("Saved")
+            .duration(Duration::from_secs(seconds))
+            .with_clock(clock.clone());
+        (toast, clock)
+    }
+
+    #[test]
+    fn tick_counts_time_from_first_tick() {
+        let mut toast = Toast::new("Saved").duration(Duration::from_secs(2));
+        toast.tick(10.0);
+        assert_eq!(toast.remaining(), Some(Duration::from_secs(2)));
+        toast.tick(11.5);
+        assert_eq!(toast.remaining(), Some(Duration::from_millis(500)));
+        assert!(!toast.has_expired());
+        toast.tick(12.0);
+        assert!(toast.has_expired());
+        assert_eq!(toast.remaining(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn pause_freezes_countdown() {
+        let ctx = Context::default();
+        let (mut toast, clock) = timed(4);
+        toast.show_hidden(&ctx, true);
+        assert!(toast.is_paused());
+        clock.advance(Duration::from_secs(3));
+        toast.show_hidden(&ctx, false);
+        assert!(!toast.is_paused());
+        assert_eq!(toast.remaining(), Some(Duration::from_secs(4)));
+        clock.advance(Duration::from_secs(1));
+        toast.show_hidden(&ctx, false);
+        assert_eq!(toast.remaining(), Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn pause_on_hover_can_be_turned_off() {
+        let ctx = Context::default();
+        let (toast, clock) = timed(4);
+        let mut toast = toast.pause_on_hover(false);
+        toast.show_hidden(&ctx, true);
+        assert!(!toast.is_paused());
+        clock.advance(Duration::from_secs(1));
+        toast.show_hidden(&ctx, true);
+        assert_eq!(toast.remaining(), Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn spinner_holds_countdown() {
+        let ctx = Context::default();
+        let (toast, clock) = timed(2);
+        let mut toast = toast.spinner(true);
+        toast.show_hidden(&ctx, false);
+        clock.advance(Duration::from_secs(10));
+        toast.show_hidden(&ctx, false);
+        assert!(!toast.has_expired());
+
+        toast = toast.spinner(false);
+        clock.advance(Duration::from_secs(2));
+        toast.show_hidden(&ctx, false);
+        assert!(toast.has_expired());
+    }
+
+    #[test]
+    fn pending_promise_is_polled_while_hidden() {
+        let ctx = Context::default();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut toast = Toast::promise("Exporting…", ToastPromise::from_receiver(receiver));
+        let run = |toast: &mut Toast| {
+            let output = ctx.run(egui::RawInput::default(), |ctx| {
+                toast.show_hidden(ctx, false);
+            });
+            output.viewport_output[&ViewportId::ROOT].repaint_delay
+        };
+        run(&mut toast);
+        assert!(run(&mut toast) <= PROMISE_POLL_INTERVAL);
+
+        sender.send(Ok("Exported".to_string())).unwrap();
+        run(&mut toast);
+        assert_eq!(toast.message, "Exported");
+        assert_eq!(toast.level, Some(AlertLevel::Success));
+        assert!(toast.promise.is_none());
+    }
+
+    #[test]
+    fn remaining_fraction_counts_down() {
+        let mut toast = Toast::new("Saved").duration(Duration::from_secs(4));
+        assert_eq!(toast.remaining_fraction(), 1.0);
+        toast.tick(0.0);
+        toast.tick(1.0);
+        assert_eq!(toast.remaining_fraction(), 0.75);
+        toast.tick(10.0);
+        assert_eq!(toast.remaining_fraction(), 0.0);
+
+        let mut zero = Toast::new("Saved").duration(Duration::ZERO);
+        zero.tick(0.0);
+        assert_eq!(zero.remaining_fraction(), 0.0);
+        assert_eq!(Toast::new("Saved").persistent().remaining_fraction(), 1.0);
+    }
+
+    #[test]
+    fn clones_share_timer() {
+        let toast = Toast::new("Saved").duration(Duration::from_secs(2));
+        toast.clone().tick(0.0);
+        toast.clone().tick(3.0);
+        assert!(toast.has_expired());
+
+        let mut detached = toast.clone();
+        detached.detach_timer();
+        detached.restart();
+        assert!(toast.has_expired());
+        assert!(!detached.has_expired());
+    }
+
+    #[test]
+    fn restart_gives_whole_duration_again() {
+        let mut toast = Toast::new("Saved").duration(Duration::from_secs(2));
+        toast.tick(0.0);
+        toast.tick(2.0);
+        assert!(toast.has_expired());
+        toast.restart();
+        assert_eq!(toast.remaining(), Some(Duration::from_secs(2)));
     }
 }
@@ -0,0 +1,191 @@
+//! # Toast Logger Module
+//!
+//! This module bridges the `log` crate into toasts. The [`ToastLogger`] is a [`log::Log`] implementation
+//! that turns records at or above a configurable level into toasts, so existing library code can report
+//! failures to users without being changed.
+//!
+//! ## Usage
+//!
+//! Create the logger from a [`ToastSender`] and install it with [`ToastLogger::init`]. Records map to
+//! toast levels as follows: `Error` becomes an error toast, `Warn` a warning toast and anything below an
+//! info toast. By default only `Warn` and `Error` records are shown.
+//!
+//! The logger can chain to another logger with [`ToastLogger::chain`], which receives every record, so
+//! terminal logging keeps working. Records from egui's own crates are never turned into toasts, as they
+//! may be logged while the egui context is locked.
+//!
+//! ## Example
+//! ```no_run
+//! # let ctx = egui::Context::default();
+//! use egui_widget_ext::{ToastLogger, ToastManager};
+//! use log::LevelFilter;
+//!
+//! ToastLogger::new(ToastManager::sender(&ctx))
+//!     .level(LevelFilter::Warn)
+//!     .init()
+//!     .expect("no other logger installed");
+//!
+//! log::error!("Could not reach the server");
+//! ```
+//!
+//! ## Components
+//! - [`ToastLogger`]: Struct implementing [`log::Log`] that posts toasts.
+
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+use crate::toast_manager::is_egui_target;
+use crate::{Toast, ToastSender};
+
+/// A [`log::Log`] implementation that turns log records into toasts.
+///
+/// Records at or above the configured level are posted through a [`ToastSender`]. Every record is also
+/// forwarded to the chained logger, if any.
+pub struct ToastLogger {
+    /// Sender used to post the toasts.
+    sender: ToastSender,
+    /// Minimum level of the records turned into toasts.
+    level: LevelFilter,
+    /// Logger receiving every record after this one.
+    next: Option<Box<dyn Log>>,
+}
+
+impl ToastLogger {
+    /// Create a logger posting `Warn` and `Error` records through the given sender.
+    pub fn new(sender: ToastSender) -> Self {
+        Self {
+            sender,
+            level: LevelFilter::Warn,
+            next: None,
+        }
+    }
+
+    /// Set the minimum level of the records turned into toasts.
+    pub fn level(mut self, level: LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Set a logger that receives every record after this one, for example a terminal logger.
+    pub fn chain(mut self, next: impl Log + 'static) -> Self {
+        self.next = Some(Box::new(next));
+        self
+    }
+
+    /// Install the logger as the global `log` logger.
+    ///
+    /// The global maximum level is set to the toast level, or to `Trace` when a chained logger decides
+    /// for itself which records it wants.
+    pub fn init(self) -> Result<(), SetLoggerError> {
+        let max_level = if self.next.is_some() {
+            LevelFilter::Trace
+        } else {
+            self.level
+        };
+        log::set_boxed_logger(Box::new(self))?;
+        log::set_max_level(max_level);
+        Ok(())
+    }
+
+    /// Check if a record with the given metadata is turned into a toast.
+    fn shows(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level && !is_egui_target(metadata.target())
+    }
+
+    /// Build the toast for a record, with the toast level matching the record level.
+    fn record_to_toast(record: &Record) -> Toast {
+        let message = record.args().to_string();
+        match record.level() {
+            Level::Error => Toast::error(&message),
+            Level::Warn => Toast::warning(&message),
+            Level::Info | Level::Debug | Level::Trace => Toast::info(&message),
+        }
+    }
+}
+
+impl Log for ToastLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.shows(metadata)
+            || self
+                .next
+                .as_ref()
+                .is_some_and(|next| next.enabled(metadata))
+    }
+
+    fn log(&self, record: &Record) {
+        if self.shows(record.metadata()) {
+            self.sender.send(Self::record_to_toast(record));
+        }
+        if let Some(next) = &self.next {
+            next.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(next) = &self.next {
+            next.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{AlertLevel, ToastManager};
+
+    /// Logger recording the messages of the records it receives.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl Log for Recorder {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+
+        fn flush(&self) {}
+    }
+
+    // The global logger can only be installed once per process, so everything is checked in one test.
+    #[test]
+    fn init_posts_toasts_and_chains() {
+        let ctx = egui::Context::default();
+        let recorder = Recorder::default();
+        ToastLogger::new(ToastManager::sender(&ctx))
+            .chain(recorder.clone())
+            .init()
+            .unwrap();
+
+        log::error!("Disk failed");
+        log::warn!("Disk almost full");
+        log::info!("Disk checked");
+        log::error!(target: "egui::context", "Context locked");
+
+        let toasts = ToastManager::with(&ctx, |manager| {
+            manager
+                .toasts()
+                .map(|toast| (toast.message.clone(), toast.level))
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(
+            toasts,
+            [
+                ("Disk failed".to_string(), Some(AlertLevel::Error)),
+                ("Disk almost full".to_string(), Some(AlertLevel::Warning)),
+            ]
+        );
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                "Disk failed",
+                "Disk almost full",
+                "Disk checked",
+                "Context locked"
+            ]
+        );
+    }
+}
@@ -0,0 +1,1838 @@
+//! # Toast Manager Module
+//!
+//! This module provides a context-wide manager that owns the queue of [`Toast`]s for an application.
+//! The manager lives in the `egui::Context` data, so any code holding a `&Context` can post a toast
+//! without threading a `Vec<Toast>` through the application.
+//!
+//! ## Usage
+//!
+//! Post toasts with [`ToastManager::add`] from anywhere and call [`ToastManager::show`] once per frame.
+//! `show` lays the toasts out in a foreground area, renders each one with the [`Toast`] widget and
+//! removes the ones that have expired or were dismissed. Each toast requests a repaint for its own expiry, so no polling
+//! repaint is needed to make toasts disappear on time.
+//!
+//! Toasts are timed with egui's input time unless a different [`ToastClock`] is set with
+//! [`ToastManager::set_clock`]. Each toast's timer starts the first time it is shown.
+//!
+//! Placement is controlled by the [`ToastAnchor`], the offset from the screen edges and the
+//! [`ToastStacking`] direction. These are plain fields of the manager, so they can be changed at runtime,
+//! for example to move the toasts when the window becomes narrow.
+//!
+//! Toasts slide in from the anchor edge, fade out when they expire or are dismissed, and the remaining
+//! toasts move smoothly into the freed space. The [`ToastAnimation`] style and its duration are
+//! configurable. Animations are turned off when [`ToastManager::reduce_motion`] is set or when the egui
+//! style disables animations (`animation_time` of zero).
+//!
+//! The number of toasts visible at once can be capped with [`ToastManager::max_visible`]. Extra toasts
+//! wait in a first in, first out queue and are promoted as visible ones go away. Their timers only start
+//! once they are shown. A "+N more" row summarizing the queue is shown unless
+//! [`ToastManager::overflow_summary`] is turned off.
+//!
+//! User interactions with managed toasts, such as clicking an action button, are reported as
+//! [`ToastEvent`]s. Read them with [`ToastManager::events`] after calling `show`; they describe what
+//! happened during that frame only.
+//!
+//! Toasts sharing a group key ([`Toast::group`](crate::Toast::group)) are collapsed into one summary
+//! reading the count and the group label ([`Toast::group_label`](crate::Toast::group_label)), such as
+//! "20 files uploaded", that takes a single place in the stack. Clicking the summary expands it with an
+//! animation to list the toasts, and its close button dismisses all of them. The toasts of a collapsed
+//! group keep counting down, so the summary disappears once all of them have expired.
+//!
+//! Keyboard users reach the toasts with [`ToastManager::focus_shortcut`] (F6 by default), which moves
+//! focus to the newest toast. The arrow keys then move between toasts, Enter triggers the focused toast's
+//! first action and Escape dismisses it. [`ToastManager::dismiss_all_shortcut`] (Shift+Escape by
+//! default) dismisses every toast at once. Both shortcuts can be changed or turned off.
+//!
+//! The [`ToastId`] returned when posting identifies the toast for as long as the manager owns it. Use it
+//! with [`ToastManager::update`] to change a live toast in place, without it moving in the stack, or
+//! with [`ToastManager::dismiss`] to remove it.
+//!
+//! Expired and dismissed toasts are kept in a bounded history, so a user who looked away can still find
+//! them, for example in a [`NotificationCenter`](crate::NotificationCenter). Each
+//! [`ToastHistoryEntry`] records when the toast was posted and whether it has been read. Toasts the user
+//! dismissed count as read. The number of entries kept is set with [`ToastManager::history_limit`]; the
+//! oldest entries are dropped first.
+//!
+//! Applications with several viewports share one manager, and each viewport keeps its own stack. Call
+//! [`ToastManager::show`] from every viewport with that viewport's context. A toast goes to the viewport
+//! chosen with [`Toast::viewport`](crate::Toast::viewport), or else to the viewport that has focus when
+//! it is first shown, the root viewport if none has. Toasts left in a viewport that closes move to the
+//! focused viewport. Events are reported per viewport as well.
+//!
+//! Code running on worker threads cannot touch the `egui::Ui`, but it can post toasts through a
+//! [`ToastSender`] obtained with [`ToastManager::sender`]. The sender is cheap to clone, `Send` and
+//! `Sync`, and wakes the UI so the toast appears on the next frame.
+//!
+//! ## Example
+//! ```
+//! # egui::__run_test_ui(|ui| {
+//! use egui_widget_ext::{
+//!     AlertLevel, Toast, ToastAnchor, ToastAnimation, ToastEvent, ToastManager, ToastStacking,
+//! };
+//!
+//! let ctx = ui.ctx();
+//! ToastManager::add(ctx, Toast::new("File saved"));
+//! ToastManager::with(ctx, |manager| {
+//!     manager.anchor = ToastAnchor::TopCenter;
+//!     manager.offset = egui::vec2(0.0, 16.0);
+//!     manager.stacking = ToastStacking::NewestOnTop;
+//!     manager.width = 300.0;
+//!     manager.animation = ToastAnimation::Fade;
+//!     manager.max_visible = Some(3);
+//! });
+//!
+//! // Once per frame, after the rest of the UI has been built.
+//! ToastManager::show(ctx);
+//! for event in ToastManager::events(ctx) {
+//!     if let ToastEvent::Action { id, action } = event {
+//!         println!("Action {action} clicked on toast {id:?}");
+//!     }
+//! }
+//!
+//! // A loading toast that later reports the outcome in place.
+//! let upload = ToastManager::add(ctx, Toast::info("Uploading…").spinner(true));
+//! ToastManager::update(ctx, upload, |toast| {
+//!     toast
+//!         .message("Uploaded")
+//!         .with_level(AlertLevel::Success)
+//!         .spinner(false)
+//! });
+//!
+//! // Posting from a worker thread.
+//! let sender = ToastManager::sender(ctx);
+//! std::thread::spawn(move || {
+//!     sender.send(Toast::warning("Connection lost"));
+//! });
+//! # });
+//! ```
+//!
+//! ## Components
+//! - [`ToastManager`]: Struct owning the toast queue and rendering it.
+//! - [`ToastId`]: Identifier returned when a toast is posted.
+//! - [`ToastEvent`]: Enum describing user interactions with managed toasts.
+//! - [`ToastHistoryEntry`]: Struct recording a toast that has left the screen.
+//! - [`ToastSender`]: Thread-safe handle for posting toasts from worker threads.
+//! - [`ToastAnchor`]: Enum for the screen position the toast stack is anchored to.
+//! - [`ToastStacking`]: Enum for the order in which toasts are stacked.
+//! - [`ToastAnimation`]: Enum for the style of the enter and exit animations.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::SystemTime;
+
+use egui::collapsing_header::CollapsingState;
+use egui::emath::easing;
+use egui::{
+    Align, Align2, Area, Button, Color32, Context, CornerRadius, EventFilter, Frame, Id, Key,
+    KeyboardShortcut, Label, Layout, Margin, Modifiers, Order, Pos2, RichText, Sense, Stroke, Ui,
+    UiBuilder, Vec2, ViewportId, ViewportIdMap, WidgetInfo, WidgetType, vec2,
+};
+
+use crate::{AlertLevel, Toast, ToastClock};
+
+/// Key under which the manager is stored in the `egui::Context` data.
+const MANAGER_ID: &str = "egui_widget_ext::toast_manager";
+
+/// Identifier assigned to a toast when it is posted to the [`ToastManager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ToastId(u64);
+
+/// A user interaction with a managed toast, reported by [`ToastManager::events`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToastEvent {
+    /// An action button of the toast was clicked.
+    Action {
+        /// The toast the action belongs to.
+        id: ToastId,
+        /// The identifier of the clicked action.
+        action: String,
+    },
+    /// The toast was dismissed by the user before it expired.
+    Dismissed {
+        /// The dismissed toast.
+        id: ToastId,
+    },
+}
+
+/// A toast that expired or was dismissed, kept in the history of the [`ToastManager`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToastHistoryEntry {
+    /// The identifier the toast had while it was shown.
+    pub id: ToastId,
+    /// The title of the toast when it left the screen, if any.
+    pub title: Option<String>,
+    /// The message of the toast when it left the screen.
+    pub message: String,
+    /// The severity level of the toast, if one was set.
+    pub level: Option<AlertLevel>,
+    /// The background color of the toast.
+    pub color: Color32,
+    /// Number of times the toast was posted, including repeats coalesced into it.
+    pub repeat_count: u32,
+    /// When the toast was posted.
+    pub posted: SystemTime,
+    /// Whether the toast was dismissed rather than expired.
+    pub dismissed: bool,
+    /// Whether the entry has been read.
+    pub read: bool,
+}
+
+/// Screen position the toast stack is anchored to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ToastAnchor {
+    /// Top left corner of the screen.
+    TopLeft,
+    /// Center of the top edge of the screen.
+    TopCenter,
+    /// Top right corner of the screen.
+    TopRight,
+    /// Bottom left corner of the screen.
+    BottomLeft,
+    /// Center of the bottom edge of the screen.
+    BottomCenter,
+    /// Bottom right corner of the screen.
+    #[default]
+    BottomRight,
+}
+
+impl ToastAnchor {
+    /// All anchors, convenient for building a selection UI.
+    pub const ALL: [ToastAnchor; 6] = [
+        ToastAnchor::TopLeft,
+        ToastAnchor::TopCenter,
+        ToastAnchor::TopRight,
+        ToastAnchor::BottomLeft,
+        ToastAnchor::BottomCenter,
+        ToastAnchor::BottomRight,
+    ];
+
+    /// Map the anchor to the matching egui alignment.
+    pub fn align(self) -> Align2 {
+        match self {
+            ToastAnchor::TopLeft => Align2::LEFT_TOP,
+            ToastAnchor::TopCenter => Align2::CENTER_TOP,
+            ToastAnchor::TopRight => Align2::RIGHT_TOP,
+            ToastAnchor::BottomLeft => Align2::LEFT_BOTTOM,
+            ToastAnchor::BottomCenter => Align2::CENTER_BOTTOM,
+            ToastAnchor::BottomRight => Align2::RIGHT_BOTTOM,
+        }
+    }
+
+    /// Turn an offset from the screen edges into a signed offset pointing into the screen.
+    ///
+    /// The horizontal offset is ignored for the center anchors.
+    fn inward(self, offset: Vec2) -> Vec2 {
+        let sign = |align: Align| match align {
+            Align::Min => 1.0,
+            Align::Center => 0.0,
+            Align::Max => -1.0,
+        };
+        let align = self.align();
+        Vec2::new(sign(align.x()) * offset.x, sign(align.y()) * offset.y)
+    }
+
+    /// Check if the anchor is on the top edge of the screen.
+    pub fn is_top(self) -> bool {
+        self.align().y() == Align::Min
+    }
+
+    /// Unit vector pointing from the stack towards the screen edge toasts slide in from.
+    ///
+    /// Corner anchors slide in horizontally, center anchors vertically.
+    fn slide_direction(self) -> Vec2 {
+        match self.align().x() {
+            Align::Min => vec2(-1.0, 0.0),
+            Align::Max => vec2(1.0, 0.0),
+            Align::Center if self.is_top() => vec2(0.0, -1.0),
+            Align::Center => vec2(0.0, 1.0),
+        }
+    }
+}
+
+/// Order in which toasts are stacked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ToastStacking {
+    /// The most recently posted toast is shown at the top of the stack.
+    NewestOnTop,
+    /// The most recently posted toast is shown at the bottom of the stack.
+    #[default]
+    NewestOnBottom,
+}
+
+/// Style of the animations played when toasts appear and disappear.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ToastAnimation {
+    /// Toasts slide in from the anchor edge and fade out.
+    #[default]
+    Slide,
+    /// Toasts fade in and fade out.
+    Fade,
+    /// Toasts appear and disappear instantly.
+    None,
+}
+
+/// A toast owned by the manager along with its identifier and layout state.
+#[derive(Debug, Clone)]
+struct ManagedToast {
+    /// Identifier handed out when the toast was posted.
+    id: ToastId,
+    /// The toast being displayed.
+    toast: Toast,
+    /// When the toast was posted.
+    posted: SystemTime,
+    /// Whether the toast has been shown at least once.
+    shown: bool,
+    /// Whether the toast is waiting in the overflow queue.
+    queued: bool,
+    /// Whether the toast has finished and is playing its exit animation.
+    leaving: bool,
+    /// How far the enter or exit animation is, from `0.0` (hidden) to `1.0` (fully shown).
+    visibility: f32,
+    /// Height of the toast in the last frame.
+    height: f32,
+    /// Id of the toast widget in the last frame, used to move keyboard focus to it.
+    focus_id: Option<Id>,
+}
+
+impl ManagedToast {
+    /// Wrap a newly posted toast, queued until it gets a visible slot.
+    fn new(id: ToastId, toast: Toast) -> Self {
+        Self {
+            id,
+            toast,
+            posted: SystemTime::now(),
+            shown: false,
+            queued: true,
+            leaving: false,
+            visibility: 0.0,
+            height: 0.0,
+            focus_id: None,
+        }
+    }
+
+    /// Render the toast and record the resulting events.
+    fn show(&mut self, ui: &mut Ui, events: &mut Vec<ToastEvent>) {
+        let response = self.toast.show(ui);
+        self.focus_id = Some(response.response.id);
+        if let Some(action) = response.action {
+            events.push(ToastEvent::Action {
+                id: self.id,
+                action,
+            });
+        }
+        if response.dismissed {
+            events.push(ToastEvent::Dismissed { id: self.id });
+        }
+    }
+
+    /// Check if the toast has expired or was dismissed.
+    fn is_finished(&self) -> bool {
+        self.toast.has_expired() || self.toast.is_dismissed()
+    }
+
+    /// Check if the toast has finished its exit animation and can be removed.
+    fn is_gone(&self) -> bool {
+        self.leaving && self.visibility <= 0.0
+    }
+
+    /// Turn a removed toast into its history entry. Dismissed toasts were seen and start out read.
+    fn into_history(self) -> ToastHistoryEntry {
+        let dismissed = self.toast.is_dismissed();
+        ToastHistoryEntry {
+            id: self.id,
+            repeat_count: self.toast.repeat_count(),
+            title: self.toast.title,
+            message: self.toast.message,
+            level: self.toast.level,
+            color: self.toast.color,
+            posted: self.posted,
+            dismissed,
+            read: dismissed,
+        }
+    }
+}
+
+/// A change to a toast requested while the toasts were taken out for rendering.
+enum PendingChange {
+    /// Replace the toast by the result of the closure.
+    Update(ToastId, Box<dyn FnOnce(Toast) -> Toast + Send>),
+    /// Dismiss the toast.
+    Dismiss(ToastId),
+}
+
+impl std::fmt::Debug for PendingChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PendingChange::Update(id, _) => f.debug_tuple("Update").field(id).finish(),
+            PendingChange::Dismiss(id) => f.debug_tuple("Dismiss").field(id).finish(),
+        }
+    }
+}
+
+/// Snapshot of the manager configuration used while rendering without holding the lock.
+#[derive(Debug, Clone, Copy)]
+struct StackSettings {
+    /// Screen position the toast stack is anchored to.
+    anchor: ToastAnchor,
+    /// Distance between the toast stack and the screen edges.
+    offset: Vec2,
+    /// Order in which toasts are stacked.
+    stacking: ToastStacking,
+    /// Width of the toast stack.
+    width: f32,
+    /// Animation style, [`ToastAnimation::None`] when motion is reduced.
+    animation: ToastAnimation,
+    /// Duration of the animations in seconds, zero when they are off.
+    animation_time: f32,
+    /// Maximum number of toasts visible at once.
+    max_visible: Option<usize>,
+    /// Whether a row summarizing the queued toasts is shown.
+    overflow_summary: bool,
+    /// Shortcut moving keyboard focus to the newest toast.
+    focus_shortcut: Option<KeyboardShortcut>,
+    /// Shortcut dismissing all toasts.
+    dismiss_all_shortcut: Option<KeyboardShortcut>,
+}
+
+/// Context-wide owner of the toast queue.
+///
+/// The `ToastManager` is stored in the `egui::Context` data and is accessed through associated
+/// functions taking a `&Context`. Use [`ToastManager::add`] to post a toast and call
+/// [`ToastManager::show`] once per frame to lay out, render and expire the toasts.
+#[derive(Debug)]
+pub struct ToastManager {
+    /// Screen position the toast stack is anchored to.
+    pub anchor: ToastAnchor,
+    /// Distance between the toast stack and the screen edges it is anchored to.
+    pub offset: Vec2,
+    /// Order in which toasts are stacked.
+    pub stacking: ToastStacking,
+    /// Width of the toast stack.
+    pub width: f32,
+    /// Style of the enter and exit animations.
+    pub animation: ToastAnimation,
+    /// Duration of the animations in seconds.
+    pub animation_time: f32,
+    /// Whether to turn animations off, for users who asked for reduced motion.
+    pub reduce_motion: bool,
+    /// Maximum number of toasts visible at once, `None` for no limit.
+    pub max_visible: Option<usize>,
+    /// Whether a "+N more" row is shown while toasts are waiting in the overflow queue.
+    pub overflow_summary: bool,
+    /// Maximum number of expired and dismissed toasts kept in the history, `0` to keep none.
+    pub history_limit: usize,
+    /// Shortcut moving keyboard focus to the newest toast, `None` to turn it off.
+    pub focus_shortcut: Option<KeyboardShortcut>,
+    /// Shortcut dismissing all toasts, `None` to turn it off.
+    pub dismiss_all_shortcut: Option<KeyboardShortcut>,
+    /// Clock handed to posted toasts that do not have one of their own.
+    clock: Option<Arc<dyn ToastClock>>,
+    /// Toasts currently owned by the manager, in the order they were posted.
+    toasts: Vec<ManagedToast>,
+    /// Events that happened during the last call to [`ToastManager::show`], per viewport.
+    events: ViewportIdMap<Vec<ToastEvent>>,
+    /// Viewport that had focus most recently, which unassigned toasts are shown in.
+    focused_viewport: ViewportId,
+    /// Toasts that have left the screen, oldest first.
+    history: VecDeque<ToastHistoryEntry>,
+    /// Toasts taken out for rendering, which the manager still owns.
+    rendering: HashSet<ToastId>,
+    /// Changes to toasts requested while rendering, applied once the toasts are back.
+    pending: Vec<PendingChange>,
+    /// Toasts posted while rendering that were coalesced once the toasts were back, mapped to the toast
+    /// they were coalesced into.
+    coalesced: HashMap<ToastId, ToastId>,
+    /// Identifier handed out to the next posted toast.
+    next_id: u64,
+}
+
+impl Default for ToastManager {
+    fn default() -> Self {
+        Self {
+            anchor: ToastAnchor::default(),
+            offset: Vec2::ZERO,
+            stacking: ToastStacking::default(),
+            width: 350.0, // Default to a width that fits most messages
+            animation: ToastAnimation::default(),
+            animation_time: 0.25,
+            reduce_motion: false,
+            max_visible: Some(5), // Default to a stack that fits on small screens
+            overflow_summary: true,
+            history_limit: 50,
+            focus_shortcut: Some(KeyboardShortcut::new(Modifiers::NONE, Key::F6)),
+            dismiss_all_shortcut: Some(KeyboardShortcut::new(Modifiers::SHIFT, Key::Escape)),
+            clock: None, // Default to egui's input time
+            toasts: Vec::new(),
+            events: ViewportIdMap::default(),
+            focused_viewport: ViewportId::ROOT,
+            history: VecDeque::new(),
+            rendering: HashSet::new(),
+            pending: Vec::new(),
+            coalesced: HashMap::new(),
+            next_id: 0,
+        }
+    }
+}
+
+impl ToastManager {
+    /// Post a toast to the manager stored in the given context.
+    ///
+    /// Returns the [`ToastId`] assigned to the toast. The viewport showing the toast is woken, so a toast
+    /// posted after [`ToastManager::show`] or between frames appears on the next frame.
+    pub fn add(ctx: &Context, toast: Toast) -> ToastId {
+        let (id, viewport) = Self::with(ctx, |manager| {
+            let id = manager.push(toast);
+            (id, manager.viewport_of(id))
+        });
+        ctx.request_repaint_of(viewport);
+        id
+    }
+
+    /// Run a closure with mutable access to the manager stored in the given context.
+    ///
+    /// This is the way to change the manager configuration or inspect its toasts.
+    ///
+    /// The closure runs while the manager is locked, and the lock is not reentrant. Calling anything that
+    /// reaches the manager from inside it deadlocks: the other `ToastManager` functions, a
+    /// [`ToastSender`], the [`NotificationBell`](crate::NotificationBell) and
+    /// [`NotificationCenter`](crate::NotificationCenter) widgets, and `log` or `tracing` calls while a
+    /// toast bridge is installed. Copy what is needed out of the closure and act on it afterwards.
+    pub fn with<R>(ctx: &Context, f: impl FnOnce(&mut ToastManager) -> R) -> R {
+        let handle = Self::handle(ctx);
+        let mut manager = lock(&handle);
+        f(&mut manager)
+    }
+
+    /// Render the toasts of the manager stored in the given context.
+    ///
+    /// Expired and dismissed toasts play their exit animation and are then moved to the history. Call this
+    /// once per frame, and in every viewport of the application, with the context passed to that
+    /// viewport. Only the toasts of the current viewport are rendered.
+    pub fn show(ctx: &Context) {
+        let handle = Self::handle(ctx);
+        let viewport = ctx.viewport_id();
+        let (focused, open) = ctx.input(|input| {
+            let open: Vec<ViewportId> = input.raw.viewports.keys().copied().collect();
+            (input.viewport().focused.unwrap_or(false), open)
+        });
+        // Take this viewport's toasts out so the lock is not held while rendering.
+        let (mut toasts, first_posted, settings) = {
+            let mut manager = lock(&handle);
+            if focused {
+                manager.focused_viewport = viewport;
+            }
+            if manager.rehome(viewport, &open) {
+                ctx.request_repaint_of(manager.focused_viewport);
+            }
+            manager.events.remove(&viewport);
+            let settings = manager.settings(ctx);
+            let claim = manager.focused_viewport == viewport;
+            let (mut toasts, others): (Vec<ManagedToast>, _) = std::mem::take(&mut manager.toasts)
+                .into_iter()
+                .partition(|entry| match entry.toast.viewport {
+                    Some(target) => target == viewport,
+                    None => claim,
+                });
+            manager.toasts = others;
+            for entry in &mut toasts {
+                entry.toast.viewport = Some(viewport);
+            }
+            manager
+                .rendering
+                .extend(toasts.iter().map(|entry| entry.id));
+            (toasts, manager.next_id, settings)
+        };
+
+        let mut events = Vec::new();
+        let mut gone = Vec::new();
+        if !toasts.is_empty() {
+            let dismiss_all = settings
+                .dismiss_all_shortcut
+                .is_some_and(|shortcut| ctx.input_mut(|input| input.consume_shortcut(&shortcut)));
+            for entry in &mut toasts {
+                // Toasts that expired or were dismissed in an earlier frame are already on their way out.
+                entry.leaving |= entry.is_finished();
+                if dismiss_all && !entry.leaving {
+                    entry.toast.dismiss();
+                    entry.leaving = true;
+                    events.push(ToastEvent::Dismissed { id: entry.id });
+                }
+            }
+            let queued = promote(&mut toasts, settings.max_visible);
+            show_stack(ctx, &mut toasts, queued, settings, &mut events);
+            (gone, toasts) = toasts.into_iter().partition(ManagedToast::is_gone);
+        }
+
+        // Toasts posted while rendering were queued behind the ones taken out and may repeat them.
+        let mut manager = lock(&handle);
+        for entry in toasts.iter().chain(&gone) {
+            manager.rendering.remove(&entry.id);
+        }
+        let split = manager
+            .toasts
+            .iter()
+            .position(|entry| entry.id.0 >= first_posted)
+            .unwrap_or(manager.toasts.len());
+        let posted = manager.toasts.split_off(split);
+        manager.toasts.extend(toasts);
+        for entry in posted {
+            let id = entry.id;
+            let kept = manager.insert(entry);
+            if kept != id {
+                manager.coalesced.insert(id, kept);
+            }
+        }
+        for change in std::mem::take(&mut manager.pending) {
+            match change {
+                PendingChange::Update(id, f) => manager.update_toast(id, f),
+                PendingChange::Dismiss(id) => manager.dismiss_toast(id),
+            };
+        }
+        for entry in gone {
+            manager.record(entry);
+        }
+        manager.events.insert(viewport, events);
+    }
+
+    /// Change a toast of the manager stored in the given context in place.
+    ///
+    /// The closure receives the toast and returns its new version, so the builder methods can be used.
+    /// The toast keeps its place in the stack, its viewport, clock and group, and its countdown restarts.
+    /// Returns `false` if the manager no longer owns a toast with the given identifier.
+    ///
+    /// A change requested while the toast is being rendered, for example from another thread, is applied
+    /// right after rendering. The closure may be kept until then, which is why it must be `Send` and
+    /// `'static`.
+    ///
+    /// The closure runs while the manager is locked, so it must not reach the manager again, see
+    /// [`ToastManager::with`].
+    pub fn update(
+        ctx: &Context,
+        id: ToastId,
+        f: impl FnOnce(Toast) -> Toast + Send + 'static,
+    ) -> bool {
+        let (updated, viewport) = Self::with(ctx, |manager| {
+            (manager.update_toast(id, f), manager.viewport_of(id))
+        });
+        ctx.request_repaint_of(viewport);
+        updated
+    }
+
+    /// Dismiss a toast of the manager stored in the given context.
+    ///
+    /// The toast plays its exit animation and is removed. Returns `false` if the manager no longer owns a
+    /// toast with the given identifier.
+    pub fn dismiss(ctx: &Context, id: ToastId) -> bool {
+        let (dismissed, viewport) = Self::with(ctx, |manager| {
+            (manager.dismiss_toast(id), manager.viewport_of(id))
+        });
+        ctx.request_repaint_of(viewport);
+        dismissed
+    }
+
+    /// Get a thread-safe handle for posting toasts to the manager stored in the given context.
+    pub fn sender(ctx: &Context) -> ToastSender {
+        ToastSender {
+            ctx: ctx.clone(),
+            manager: Self::handle(ctx),
+        }
+    }
+
+    /// Get the events that happened during the last call to [`ToastManager::show`] for the viewport of
+    /// the given context.
+    pub fn events(ctx: &Context) -> Vec<ToastEvent> {
+        let viewport = ctx.viewport_id();
+        Self::with(ctx, |manager| {
+            manager.events.get(&viewport).cloned().unwrap_or_default()
+        })
+    }
+
+    /// Post a toast to this manager.
+    ///
+    /// A toast with the same dedupe key as a live toast refreshes that toast instead of being appended.
+    /// Returns the [`ToastId`] assigned to the toast, or the one of the toast it was coalesced into. A
+    /// toast posted while the toasts are rendered is coalesced once they are back, and the identifier
+    /// returned for it then refers to the toast it was coalesced into.
+    pub fn push(&mut self, mut toast: Toast) -> ToastId {
+        if toast.clock.is_none() {
+            toast.clock = self.clock.clone();
+        }
+        toast.detach_timer();
+        let id = ToastId(self.next_id);
+        self.next_id += 1;
+        self.insert(ManagedToast::new(id, toast))
+    }
+
+    /// Replace the toast with the given identifier by the result of the closure, restarting its countdown.
+    ///
+    /// The new toast keeps the viewport, clock and group of the old one, so a toast built from scratch does
+    /// not move to another stack. A toast that expired and is fading out is shown again, unless it was
+    /// dismissed. The change to a toast taken out for rendering is deferred until it is
+    /// back.
+    fn update_toast(
+        &mut self,
+        id: ToastId,
+        f: impl FnOnce(Toast) -> Toast + Send + 'static,
+    ) -> bool {
+        let id = self.resolve(id);
+        if let Some(entry) = self.toasts.iter_mut().find(|entry| entry.id == id) {
+            let old = std::mem::take(&mut entry.toast);
+            let (viewport, clock, group) = (old.viewport, old.clock.clone(), old.group.clone());
+            let toast = &mut entry.toast;
+            *toast = f(old);
+            toast.viewport = viewport;
+            toast.clock = clock;
+            toast.group = group;
+            toast.restart();
+            // A toast that expired while its exit animation plays comes back with the change.
+            entry.leaving = entry.is_finished();
+            return true;
+        }
+        if self.rendering.contains(&id) {
+            self.pending.push(PendingChange::Update(id, Box::new(f)));
+            return true;
+        }
+        false
+    }
+
+    /// Dismiss the toast with the given identifier.
+    ///
+    /// Dismissing a toast taken out for rendering is deferred until it is back.
+    fn dismiss_toast(&mut self, id: ToastId) -> bool {
+        let id = self.resolve(id);
+        if let Some(toast) = self.get_mut(id) {
+            toast.dismiss();
+            return true;
+        }
+        if self.rendering.contains(&id) {
+            self.pending.push(PendingChange::Dismiss(id));
+            return true;
+        }
+        false
+    }
+
+    /// Add an already identified toast, coalescing it into a live toast with the same dedupe key.
+    ///
+    /// A toast is only coalesced into a toast shown in the same viewport, counting unassigned toasts as
+    /// shown in the focused viewport.
+    fn insert(&mut self, new: ManagedToast) -> ToastId {
+        let focused = self.focused_viewport;
+        let viewport = new.toast.viewport.unwrap_or(focused);
+        let existing = new.toast.coalesce_key().and_then(|key| {
+            self.toasts.iter_mut().find(|entry| {
+                !entry.leaving
+                    && !entry.is_finished()
+                    && entry.toast.viewport.unwrap_or(focused) == viewport
+                    && entry.toast.coalesce_key().as_deref() == Some(key.as_str())
+            })
+        });
+        if let Some(entry) = existing {
+            entry.toast.coalesce(new.toast);
+            return entry.id;
+        }
+        let id = new.id;
+        self.toasts.push(new);
+        id
+    }
+
+    /// Release the toasts left in viewports that have closed, so the focused viewport picks them up.
+    ///
+    /// Only toasts that have been shown are released. A toast aimed at a viewport that has not opened yet
+    /// keeps waiting for it. Returns `true` if any toast was released.
+    fn rehome(&mut self, current: ViewportId, open: &[ViewportId]) -> bool {
+        // Integrations that do not report their viewports would otherwise lose every toast.
+        if open.is_empty() {
+            return false;
+        }
+        let mut released = false;
+        for entry in &mut self.toasts {
+            let closed = entry
+                .toast
+                .viewport
+                .is_some_and(|viewport| viewport != current && !open.contains(&viewport));
+            if entry.shown && closed {
+                entry.toast.viewport = None;
+                entry.shown = false;
+                released = true;
+            }
+        }
+        released
+    }
+
+    /// Identifier of the toast a toast posted while rendering was coalesced into, or the given one.
+    fn resolve(&self, id: ToastId) -> ToastId {
+        self.coalesced.get(&id).copied().unwrap_or(id)
+    }
+
+    /// Viewport the toast with the given identifier is shown in, or will be shown in once it is assigned.
+    fn viewport_of(&self, id: ToastId) -> ViewportId {
+        self.get(id)
+            .and_then(|toast| toast.viewport)
+            .unwrap_or(self.focused_viewport)
+    }
+
+    /// Set the clock used to time toasts posted from now on that do not have a clock of their own.
+    pub fn set_clock(&mut self, clock: impl ToastClock + 'static) {
+        self.clock = Some(Arc::new(clock));
+    }
+
+    /// Get the toast with the given identifier, if it is still owned by the manager.
+    pub fn get(&self, id: ToastId) -> Option<&Toast> {
+        let id = self.resolve(id);
+        self.toasts
+            .iter()
+            .find(|entry| entry.id == id)
+            .map(|entry| &entry.toast)
+    }
+
+    /// Get mutable access to the toast with the given identifier, if it is still owned by the manager.
+    pub fn get_mut(&mut self, id: ToastId) -> Option<&mut Toast> {
+        let id = self.resolve(id);
+        self.toasts
+            .iter_mut()
+            .find(|entry| entry.id == id)
+            .map(|entry| &mut entry.toast)
+    }
+
+    /// Toasts currently owned by the manager, in the order they were posted.
+    #[cfg(all(test, any(feature = "log", feature = "tracing")))]
+    pub(crate) fn toasts(&self) -> impl Iterator<Item = &Toast> {
+        self.toasts.iter().map(|entry| &entry.toast)
+    }
+
+    /// Number of toasts currently owned by the manager.
+    pub fn len(&self) -> usize {
+        self.toasts.len()
+    }
+
+    /// Check if the manager currently owns no toasts.
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+
+    /// Remove all toasts from the manager.
+    ///
+    /// The removed toasts are not added to the history.
+    pub fn clear(&mut self) {
+        self.toasts.clear();
+        self.coalesced.clear();
+    }
+
+    /// Toasts that have expired or were dismissed, oldest first.
+    pub fn history(&self) -> &VecDeque<ToastHistoryEntry> {
+        &self.history
+    }
+
+    /// Number of history entries that have not been read.
+    pub fn unread_count(&self) -> usize {
+        self.history.iter().filter(|entry| !entry.read).count()
+    }
+
+    /// Mark the history entry of the toast with the given identifier as read.
+    ///
+    /// Returns `false` if the history holds no entry for the identifier.
+    pub fn mark_read(&mut self, id: ToastId) -> bool {
+        match self.history.iter_mut().find(|entry| entry.id == id) {
+            Some(entry) => {
+                entry.read = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Mark all history entries as read.
+    pub fn mark_all_read(&mut self) {
+        for entry in &mut self.history {
+            entry.read = true;
+        }
+    }
+
+    /// Remove all entries from the history.
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Add a removed toast to the history, dropping the oldest entries beyond the limit.
+    fn record(&mut self, entry: ManagedToast) {
+        self.coalesced.retain(|_, kept| *kept != entry.id);
+        self.history.push_back(entry.into_history());
+        while self.history.len() > self.history_limit {
+            self.history.pop_front();
+        }
+    }
+
+    /// Snapshot the configuration used to render the stack, with animations off for reduced motion.
+    fn settings(&self, ctx: &Context) -> StackSettings {
+        let reduce_motion = self.reduce_motion || ctx.style().animation_time <= 0.0;
+        let animation = if reduce_motion {
+            ToastAnimation::None
+        } else {
+            self.animation
+        };
+        StackSettings {
+            anchor: self.anchor,
+            offset: self.offset,
+            stacking: self.stacking,
+            width: self.width,
+            animation,
+            animation_time: if animation == ToastAnimation::None {
+                0.0
+            } else {
+                self.animation_time
+            },
+            max_visible: self.max_visible,
+            overflow_summary: self.overflow_summary,
+            focus_shortcut: self.focus_shortcut,
+            dismiss_all_shortcut: self.dismiss_all_shortcut,
+        }
+    }
+
+    /// Fetch the shared manager from the context data, creating it on first use.
+    fn handle(ctx: &Context) -> Arc<Mutex<ToastManager>> {
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_default::<Arc<Mutex<ToastManager>>>(Id::new(MANAGER_ID))
+                .clone()
+        })
+    }
+}
+
+/// Promote queued toasts into free visible slots, oldest first, and return how many remain queued.
+///
+/// Toasts playing their exit animation do not take up a slot. All toasts of a group share one slot, so
+/// a toast joining a visible group is promoted right away.
+fn promote(toasts: &mut [ManagedToast], max_visible: Option<usize>) -> usize {
+    let mut groups = HashSet::new();
+    let mut visible = 0;
+    for entry in toasts
+        .iter()
+        .filter(|entry| !entry.queued && !entry.leaving)
+    {
+        match &entry.toast.group {
+            Some(key) => {
+                if groups.insert(key.clone()) {
+                    visible += 1;
+                }
+            }
+            None => visible += 1,
+        }
+    }
+    let mut free = max_visible.map_or(usize::MAX, |max| max.saturating_sub(visible));
+    let mut queued = 0;
+    for entry in toasts.iter_mut().filter(|entry| entry.queued) {
+        let joins_group = entry
+            .toast
+            .group
+            .as_ref()
+            .is_some_and(|key| groups.contains(key));
+        if joins_group {
+            entry.queued = false;
+        } else if free > 0 {
+            entry.queued = false;
+            free -= 1;
+            if let Some(key) = &entry.toast.group {
+                groups.insert(key.clone());
+            }
+        } else {
+            queued += 1;
+        }
+    }
+    queued
+}
+
+/// Thread-safe handle for posting toasts to a [`ToastManager`] from any thread.
+///
+/// Obtain one with [`ToastManager::sender`]. Every call wakes the viewport showing the toast so the
+/// change shows up on the next frame, through the same rendering path as toasts posted from the UI thread.
+#[derive(Debug, Clone)]
+pub struct ToastSender {
+    /// Context to wake after posting.
+    ctx: Context,
+    /// The manager the toasts are posted to.
+    manager: Arc<Mutex<ToastManager>>,
+}
+
+impl ToastSender {
+    /// Post a toast and wake the UI.
+    ///
+    /// Returns the [`ToastId`] assigned to the toast.
+    pub fn send(&self, toast: Toast) -> ToastId {
+        let (id, viewport) = {
+            let mut manager = lock(&self.manager);
+            let id = manager.push(toast);
+            (id, manager.viewport_of(id))
+        };
+        self.ctx.request_repaint_of(viewport);
+        id
+    }
+
+    /// Change a posted toast in place and wake the UI, see [`ToastManager::update`].
+    pub fn update(&self, id: ToastId, f: impl FnOnce(Toast) -> Toast + Send + 'static) -> bool {
+        let (updated, viewport) = {
+            let mut manager = lock(&self.manager);
+            (manager.update_toast(id, f), manager.viewport_of(id))
+        };
+        self.ctx.request_repaint_of(viewport);
+        updated
+    }
+
+    /// Dismiss a posted toast and wake the UI, see [`ToastManager::dismiss`].
+    pub fn dismiss(&self, id: ToastId) -> bool {
+        let (dismissed, viewport) = {
+            let mut manager = lock(&self.manager);
+            (manager.dismiss_toast(id), manager.viewport_of(id))
+        };
+        self.ctx.request_repaint_of(viewport);
+        dismissed
+    }
+}
+
+/// Check if a log target belongs to one of egui's own crates.
+///
+/// The logging bridges skip these records, since egui may log while its context is locked and posting a
+/// toast from there would deadlock on the repaint request.
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub(crate) fn is_egui_target(target: &str) -> bool {
+    // Match whole crate names, so crates like this one whose name starts with `egui` still get toasts.
+    ["egui", "epaint", "eframe"].iter().any(|name| {
+        target
+            .strip_prefix(name)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+    })
+}
+
+/// Lay out and render a stack of toasts, each toast or group in its own foreground area.
+///
+/// Toasts are placed outward from the anchor, the one closest to the screen edge first. Their distance
+/// from the anchor is animated so the stack closes gaps smoothly.
+fn show_stack(
+    ctx: &Context,
+    toasts: &mut [ManagedToast],
+    queued: usize,
+    settings: StackSettings,
+    events: &mut Vec<ToastEvent>,
+) {
+    let anchor = settings.anchor;
+    let layout = StackLayout {
+        origin: anchor.align().pos_in_rect(&ctx.screen_rect()) + anchor.inward(settings.offset),
+        outward: if anchor.is_top() { 1.0 } else { -1.0 },
+        settings,
+    };
+    let newest_near_edge = (settings.stacking == ToastStacking::NewestOnTop) == anchor.is_top();
+
+    let mut order: Vec<usize> = (0..toasts.len())
+        .filter(|&index| !toasts[index].queued)
+        .collect();
+    if newest_near_edge {
+        order.reverse();
+    }
+
+    let mut distance = 0.0;
+    for slot in slots(toasts, &order) {
+        distance += match slot {
+            Slot::Single(index) => show_single(ctx, &mut toasts[index], layout, distance, events),
+            Slot::Group(key, members) => {
+                show_group(ctx, toasts, &key, &members, layout, distance, events)
+            }
+        };
+    }
+
+    navigate(ctx, toasts, &order, settings);
+
+    // The summary sits at the far end of the stack, where the queued toasts will appear.
+    if settings.overflow_summary && queued > 0 {
+        let id = Id::new(MANAGER_ID).with("overflow").with(ctx.viewport_id());
+        let shown_distance =
+            ctx.animate_value_with_time(id.with("distance"), distance, settings.animation_time);
+        Area::new(id)
+            .order(Order::Foreground)
+            .pivot(anchor.align())
+            .fixed_pos(layout.origin + vec2(0.0, layout.outward * shown_distance))
+            .constrain(false)
+            .interactable(false)
+            .show(ctx, |ui| {
+                ui.set_width(settings.width);
+                Frame::default()
+                    .fill(Color32::from_rgb(230, 230, 230))
+                    .stroke(Stroke::new(1.0, Color32::from_rgb(200, 200, 200)))
+                    .corner_radius(CornerRadius::same(4))
+                    .inner_margin(Margin::symmetric(10, 4))
+                    .outer_margin(Margin::same(10))
+                    .show(ui, |ui| {
+                        ui.set_width(ui.available_width());
+                        ui.add(Label::new(
+                            RichText::new(format!("+{queued} more")).color(Color32::BLACK),
+                        ));
+                    });
+            });
+    }
+}
+
+/// A unit of the stack, laid out in its own area.
+enum Slot {
+    /// A toast shown on its own, by index.
+    Single(usize),
+    /// The group key and the indices of the toasts in the group, in stack order.
+    Group(String, Vec<usize>),
+}
+
+/// Split the visible toasts into slots, in stack order.
+///
+/// A group takes the place of its first member. A group key used by a single toast does not form a
+/// group, that toast is shown on its own.
+fn slots(toasts: &[ManagedToast], order: &[usize]) -> Vec<Slot> {
+    let mut slots: Vec<Slot> = Vec::new();
+    for &index in order {
+        let Some(key) = &toasts[index].toast.group else {
+            slots.push(Slot::Single(index));
+            continue;
+        };
+        let group = slots.iter_mut().find_map(|slot| match slot {
+            Slot::Group(group, members) if group == key => Some(members),
+            _ => None,
+        });
+        match group {
+            Some(members) => members.push(index),
+            None => slots.push(Slot::Group(key.clone(), vec![index])),
+        }
+    }
+    slots
+        .into_iter()
+        .map(|slot| match slot {
+            Slot::Group(_, members) if members.len() == 1 => Slot::Single(members[0]),
+            slot => slot,
+        })
+        .collect()
+}
+
+/// Position of the stack on screen during one frame.
+#[derive(Debug, Clone, Copy)]
+struct StackLayout {
+    /// Point on the anchor edge the stack grows from.
+    origin: Pos2,
+    /// Vertical direction the stack grows in, `1.0` downward and `-1.0` upward.
+    outward: f32,
+    /// Configuration of the stack.
+    settings: StackSettings,
+}
+
+impl StackLayout {
+    /// Animate the visibility and distance from the anchor of a slot and build its area.
+    ///
+    /// Returns the area, the visibility of the slot and the opacity to render it with.
+    fn area(
+        &self,
+        ctx: &Context,
+        id: Id,
+        visible: bool,
+        leaving: bool,
+        distance: f32,
+        height: f32,
+    ) -> (Area, f32, f32) {
+        let settings = self.settings;
+        let anchor = settings.anchor;
+        let visibility =
+            ctx.animate_bool_with_time(id.with("visible"), visible, settings.animation_time);
+        let shown_distance =
+            ctx.animate_value_with_time(id.with("distance"), distance, settings.animation_time);
+
+        let (slide, opacity) = match settings.animation {
+            ToastAnimation::Slide if !leaving => {
+                let travel = if anchor.slide_direction().x == 0.0 {
+                    height + settings.offset.y
+                } else {
+                    settings.width + settings.offset.x
+                };
+                let remaining = 1.0 - easing::cubic_out(visibility);
+                (anchor.slide_direction() * travel * remaining, 1.0)
+            }
+            ToastAnimation::Slide | ToastAnimation::Fade => (Vec2::ZERO, visibility),
+            ToastAnimation::None => (Vec2::ZERO, 1.0),
+        };
+
+        let area = Area::new(id)
+            .order(Order::Foreground)
+            .pivot(anchor.align())
+            .fixed_pos(self.origin + vec2(0.0, self.outward * shown_distance) + slide)
+            .constrain(false)
+            .interactable(!leaving);
+        (area, visibility, opacity)
+    }
+}
+
+/// Render a toast on its own and return its height.
+fn show_single(
+    ctx: &Context,
+    entry: &mut ManagedToast,
+    layout: StackLayout,
+    distance: f32,
+    events: &mut Vec<ToastEvent>,
+) -> f32 {
+    let id = Id::new(MANAGER_ID).with(entry.id);
+    // Stay hidden during the first frame, which egui uses to size the area.
+    let visible = entry.shown && !entry.leaving;
+    entry.shown = true;
+    let (area, visibility, opacity) =
+        layout.area(ctx, id, visible, entry.leaving, distance, entry.height);
+    entry.visibility = visibility;
+
+    let area = area.show(ctx, |ui| {
+        ui.set_width(layout.settings.width);
+        ui.multiply_opacity(opacity);
+        entry.show(ui, events);
+    });
+    entry.height = area.response.rect.height();
+    entry.height
+}
+
+/// Render the toasts of a group as a summary that expands to list them, and return its height.
+///
+/// The members of a collapsed group are not shown, but their countdowns keep running so the group
+/// goes away once all of them have expired. Hovering the summary pauses them. Members that finish
+/// while others remain disappear from the group right away; the group plays the exit animation once
+/// the last one finishes.
+fn show_group(
+    ctx: &Context,
+    toasts: &mut [ManagedToast],
+    key: &str,
+    members: &[usize],
+    layout: StackLayout,
+    distance: f32,
+    events: &mut Vec<ToastEvent>,
+) -> f32 {
+    let id = Id::new(MANAGER_ID)
+        .with("group")
+        .with(key)
+        .with(ctx.viewport_id());
+    let all_leaving = members.iter().all(|&index| toasts[index].leaving);
+    // Stay hidden during the first frame, which egui uses to size the area.
+    let visible = members.iter().any(|&index| toasts[index].shown) && !all_leaving;
+    let height = toasts[members[0]].height;
+    let (area, visibility, opacity) = layout.area(ctx, id, visible, all_leaving, distance, height);
+    for &index in members {
+        let entry = &mut toasts[index];
+        entry.shown = true;
+        entry.visibility = if entry.leaving && !all_leaving {
+            0.0
+        } else {
+            visibility
+        };
+    }
+
+    // While the whole group plays its exit animation, keep showing the members it had.
+    let live: Vec<usize> = members
+        .iter()
+        .copied()
+        .filter(|&index| all_leaving || !toasts[index].leaving)
+        .collect();
+    let color = toasts[live[0]].toast.color;
+    // The most recently posted member with a label names the group.
+    let label = members
+        .iter()
+        .filter_map(|&index| {
+            let entry = &toasts[index];
+            entry
+                .toast
+                .group_label
+                .as_deref()
+                .map(|label| (entry.id.0, label))
+        })
+        .max_by_key(|&(id, _)| id)
+        .map_or(key, |(_, label)| label);
+    let summary = format!("{} {label}", live.len());
+
+    let area = area.show(ctx, |ui| {
+        ui.set_width(layout.settings.width);
+        ui.multiply_opacity(opacity);
+        let mut state = CollapsingState::load_with_default_open(ui.ctx(), id.with("open"), false);
+        let header = group_header_ui(ui, &summary, color, state.is_open());
+        if header.toggled {
+            state.toggle(ui);
+        }
+        if header.dismissed {
+            for &index in &live {
+                toasts[index].toast.dismiss();
+                events.push(ToastEvent::Dismissed {
+                    id: toasts[index].id,
+                });
+            }
+        }
+        let expanded = state.show_body_unindented(ui, |ui| {
+            for &index in &live {
+                toasts[index].show(ui, events);
+            }
+        });
+        state.store(ui.ctx());
+        if expanded.is_none() {
+            for &index in &live {
+                toasts[index].toast.show_hidden(ui.ctx(), header.hovered);
+                toasts[index].focus_id = None;
+            }
+        }
+    });
+
+    let height = area.response.rect.height();
+    for &index in members {
+        toasts[index].height = height;
+    }
+    height
+}
+
+/// Interactions with the summary of a group during one frame.
+#[derive(Debug, Default)]
+struct GroupHeader {
+    /// Whether the summary was clicked to expand or collapse the group.
+    toggled: bool,
+    /// Whether the close button was clicked to dismiss the whole group.
+    dismissed: bool,
+    /// Whether the pointer is over the summary.
+    hovered: bool,
+}
+
+/// Render the summary of a group, styled like a toast, with an expand indicator and a close button.
+fn group_header_ui(ui: &mut Ui, label: &str, color: Color32, open: bool) -> GroupHeader {
+    let mut header = GroupHeader::default();
+    let frame = Frame::default()
+        .fill(color)
+        .stroke(Stroke::new(1.0, Color32::from_rgb(200, 200, 200)))
+        .corner_radius(CornerRadius::same(4))
+        .inner_margin(Margin::same(10))
+        .outer_margin(Margin::same(10));
+    let scope = ui.scope_builder(UiBuilder::new().sense(Sense::click()), |ui| {
+        frame.show(ui, |ui| {
+            ui.horizontal(|ui| {
+                let indicator = if open { "⏷" } else { "⏵" };
+                ui.label(RichText::new(indicator).color(Color32::BLACK));
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    let close_resp = ui.add(
+                        Button::new(RichText::new("X").color(Color32::DARK_RED).strong())
+                            .frame(false),
+                    );
+                    close_resp.widget_info(|| {
+                        WidgetInfo::labeled(WidgetType::Button, ui.is_enabled(), "Dismiss group")
+                    });
+                    header.dismissed = close_resp.clicked();
+                    ui.add(
+                        Label::new(RichText::new(label).color(Color32::BLACK).strong())
+                            .wrap()
+                            .selectable(false),
+                    );
+                });
+            });
+        });
+    });
+    let response = scope.response;
+    response.widget_info(|| {
+        WidgetInfo::selected(WidgetType::CollapsingHeader, ui.is_enabled(), open, label)
+    });
+    header.toggled = response.clicked();
+    header.hovered = response.contains_pointer();
+    header
+}
+
+/// Move keyboard focus into the stack on the focus shortcut, and between toasts with the arrow keys.
+///
+/// The order lists the toasts outward from the anchor, as they are laid out.
+fn navigate(ctx: &Context, toasts: &[ManagedToast], order: &[usize], settings: StackSettings) {
+    let reachable: Vec<(usize, Id)> = order
+        .iter()
+        .filter(|&&index| !toasts[index].leaving)
+        .filter_map(|&index| toasts[index].focus_id.map(|id| (index, id)))
+        .collect();
+    if reachable.is_empty() {
+        return;
+    }
+
+    let focused = ctx.memory(|memory| memory.focused());
+    let position = focused.and_then(|focused| reachable.iter().position(|&(_, id)| id == focused));
+    let target = match position {
+        Some(position) => {
+            // Keep egui's own arrow key navigation from moving the focus out of the stack.
+            let (_, id) = reachable[position];
+            let filter = EventFilter {
+                vertical_arrows: true,
+                ..Default::default()
+            };
+            ctx.memory_mut(|memory| memory.set_focus_lock_filter(id, filter));
+
+            let (up, down) = ctx.input_mut(|input| {
+                (
+                    input.consume_key(Modifiers::NONE, Key::ArrowUp),
+                    input.consume_key(Modifiers::NONE, Key::ArrowDown),
+                )
+            });
+            // Moving outward from the anchor is moving up for a stack at the bottom of the screen.
+            let outward = if settings.anchor.is_top() { down } else { up };
+            let inward = if settings.anchor.is_top() { up } else { down };
+            if outward {
+                reachable.get(position + 1)
+            } else if inward {
+                position
+                    .checked_sub(1)
+                    .and_then(|position| reachable.get(position))
+            } else {
+                None
+            }
+        }
+        // The newest toast was posted last.
+        None => settings
+            .focus_shortcut
+            .filter(|shortcut| ctx.input_mut(|input| input.consume_shortcut(shortcut)))
+            .and_then(|_| reachable.iter().max_by_key(|&&(index, _)| index)),
+    };
+
+    if let Some(&(_, id)) = target {
+        ctx.memory_mut(|memory| memory.request_focus(id));
+    }
+}
+
+/// Lock the manager, recovering the data if a previous holder panicked.
+fn lock(handle: &Mutex<ToastManager>) -> MutexGuard<'_, ToastManager> {
+    handle.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::ManualClock;
+
+    /// Wrap toasts as the manager does when they are posted, with identifiers in posting order.
+    fn managed(toasts: impl IntoIterator<Item = Toast>) -> Vec<ManagedToast> {
+        toasts
+            .into_iter()
+            .enumerate()
+            .map(|(id, toast)| ManagedToast::new(ToastId(id as u64), toast))
+            .collect()
+    }
+
+    /// Identifiers of the toasts that are not queued.
+    fn visible(toasts: &[ManagedToast]) -> Vec<u64> {
+        toasts
+            .iter()
+            .filter(|entry| !entry.queued)
+            .map(|entry| entry.id.0)
+            .collect()
+    }
+
+    #[test]
+    fn promote_fills_free_slots_first_in_first_out() {
+        let mut toasts = managed((0..5).map(|i| Toast::new(&format!("Toast {i}"))));
+        assert_eq!(promote(&mut toasts, Some(2)), 3);
+        assert_eq!(visible(&toasts), [0, 1]);
+
+        // A toast playing its exit animation frees its slot for the oldest queued toast.
+        toasts[0].leaving = true;
+        assert_eq!(promote(&mut toasts, Some(2)), 2);
+        assert_eq!(visible(&toasts), [0, 1, 2]);
+    }
+
+    #[test]
+    fn promote_without_limit_shows_everything() {
+        let mut toasts = managed((0..5).map(|i| Toast::new(&format!("Toast {i}"))));
+        assert_eq!(promote(&mut toasts, None), 0);
+        assert_eq!(visible(&toasts), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn promote_counts_a_group_as_one_slot() {
+        let mut toasts = managed([
+            Toast::new("a.png").group("files"),
+            Toast::new("Saved"),
+            Toast::new("b.png").group("files"),
+            Toast::new("Synced"),
+            Toast::new("c.png").group("files"),
+        ]);
+        assert_eq!(promote(&mut toasts, Some(2)), 1);
+        assert_eq!(visible(&toasts), [0, 1, 2, 4]);
+    }
+
+    /// Run a frame of the root viewport at the given time and return how long until egui is asked to run
+    /// the next one.
+    fn run(ctx: &Context, time: f64, ui: impl FnMut(&Context)) -> Duration {
+        let input = egui::RawInput {
+            time: Some(time),
+            ..Default::default()
+        };
+        let output = ctx.run(input, ui);
+        output.viewport_output[&ViewportId::ROOT].repaint_delay
+    }
+
+    #[test]
+    fn adding_after_show_wakes_the_ui() {
+        let ctx = Context::default();
+        run(&ctx, 0.0, ToastManager::show);
+        assert_eq!(run(&ctx, 1.0, ToastManager::show), Duration::MAX);
+
+        let delay = run(&ctx, 2.0, |ctx| {
+            ToastManager::show(ctx);
+            ToastManager::add(ctx, Toast::new("Saved"));
+        });
+        assert_eq!(delay, Duration::ZERO);
+    }
+
+    #[test]
+    fn updating_keeps_the_viewport_clock_and_group() {
+        let mut manager = ToastManager::default();
+        manager.set_clock(ManualClock::new(0.0));
+        let other = ViewportId::from_hash_of("other");
+        let id = manager.push(Toast::info("Uploading…").viewport(other).group("uploads"));
+
+        assert!(manager.update_toast(id, |_| Toast::success("Uploaded")));
+        let toast = manager.get(id).unwrap();
+        assert_eq!(toast.message, "Uploaded");
+        assert_eq!(toast.viewport, Some(other));
+        assert_eq!(toast.group.as_deref(), Some("uploads"));
+        assert!(toast.clock.is_some());
+        assert!(!manager.update_toast(ToastId(7), |toast| toast));
+    }
+
+    #[test]
+    fn updating_a_fading_toast_brings_it_back() {
+        let ctx = Context::default();
+        let id = ToastManager::add(
+            &ctx,
+            Toast::info("Uploading…").duration(Duration::from_secs(1)),
+        );
+        run(&ctx, 0.0, ToastManager::show);
+        run(&ctx, 1.05, ToastManager::show);
+        run(&ctx, 1.1, ToastManager::show);
+        let leaving = ToastManager::with(&ctx, |manager| manager.toasts[0].leaving);
+        assert!(leaving);
+
+        assert!(ToastManager::update(&ctx, id, |toast| toast.message("Uploaded")));
+        for frame in 0..10 {
+            run(&ctx, 1.15 + 0.05 * frame as f64, ToastManager::show);
+        }
+        let message = ToastManager::with(&ctx, |manager| {
+            manager.get(id).map(|toast| toast.message.clone())
+        });
+        assert_eq!(message.as_deref(), Some("Uploaded"));
+    }
+
+    #[test]
+    fn updating_after_show_wakes_the_ui() {
+        let ctx = Context::default();
+        ToastManager::with(&ctx, |manager| manager.reduce_motion = true);
+        let id = ToastManager::add(&ctx, Toast::info("Uploading…").persistent());
+        run(&ctx, 0.0, ToastManager::show);
+        run(&ctx, 1.0, ToastManager::show);
+        assert_eq!(run(&ctx, 2.0, ToastManager::show), Duration::MAX);
+
+        let delay = run(&ctx, 3.0, |ctx| {
+            ToastManager::show(ctx);
+            ToastManager::update(ctx, id, |toast| toast.message("Uploaded"));
+        });
+        assert_eq!(delay, Duration::ZERO);
+    }
+
+    #[test]
+    fn toast_sent_while_rendering_keeps_a_live_id() {
+        let ctx = Context::default();
+        let sender = ToastManager::sender(&ctx);
+        let first = ToastManager::add(&ctx, Toast::info("Uploading…"));
+        // The custom body runs while the toasts are taken out, like a worker thread posting mid-frame.
+        let sent = Arc::new(Mutex::new(None));
+        let slot = sent.clone();
+        ToastManager::add(
+            &ctx,
+            Toast::custom(move |_ui| {
+                slot.lock()
+                    .unwrap()
+                    .get_or_insert_with(|| sender.send(Toast::info("Uploading…")));
+            }),
+        );
+        run(&ctx, 0.0, ToastManager::show);
+
+        let id = sent.lock().unwrap().unwrap();
+        assert_ne!(id, first);
+        assert!(ToastManager::update(&ctx, id, |toast| toast.message("Uploaded")));
+        ToastManager::with(&ctx, |manager| {
+            assert_eq!(manager.len(), 2);
+            let toast = manager.get(first).unwrap();
+            assert_eq!(toast.message, "Uploaded");
+            assert_eq!(toast.repeat_count(), 2);
+        });
+        assert!(ToastManager::dismiss(&ctx, id));
+        let dismissed =
+            ToastManager::with(&ctx, |manager| manager.get(first).unwrap().is_dismissed());
+        assert!(dismissed);
+    }
+
+    /// Run a frame of `viewport` at the given time with `open` viewports, of which `focused` has focus.
+    fn run_in(
+        ctx: &Context,
+        viewport: ViewportId,
+        open: &[ViewportId],
+        focused: ViewportId,
+        time: f64,
+    ) {
+        let input = egui::RawInput {
+            viewport_id: viewport,
+            viewports: open
+                .iter()
+                .map(|&id| {
+                    let info = egui::ViewportInfo {
+                        focused: Some(id == focused),
+                        ..Default::default()
+                    };
+                    (id, info)
+                })
+                .collect(),
+            time: Some(time),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, ToastManager::show);
+    }
+
+    /// Viewport the toast with the given identifier is assigned to.
+    fn assigned(ctx: &Context, id: ToastId) -> Option<ViewportId> {
+        ToastManager::with(ctx, |manager| {
+            manager.get(id).and_then(|toast| toast.viewport)
+        })
+    }
+
+    #[test]
+    fn toasts_go_to_the_focused_viewport_and_expire_into_history() {
+        let ctx = Context::default();
+        ToastManager::with(&ctx, |manager| manager.reduce_motion = true);
+        let child = ViewportId::from_hash_of("child");
+        let open = [ViewportId::ROOT, child];
+        let id = ToastManager::add(&ctx, Toast::info("Saved").duration(Duration::from_secs(2)));
+        let aimed = ToastManager::add(&ctx, Toast::info("Synced").viewport(ViewportId::ROOT));
+
+        run_in(&ctx, child, &open, child, 0.0);
+        run_in(&ctx, ViewportId::ROOT, &open, child, 0.0);
+        assert_eq!(assigned(&ctx, id), Some(child));
+        assert_eq!(assigned(&ctx, aimed), Some(ViewportId::ROOT));
+
+        // Another viewport rendering leaves the toast alone.
+        run_in(&ctx, ViewportId::ROOT, &open, ViewportId::ROOT, 3.0);
+        assert_eq!(assigned(&ctx, id), Some(child));
+
+        // The toast expires in the first frame and is moved to the history in the next.
+        run_in(&ctx, child, &open, ViewportId::ROOT, 3.0);
+        run_in(&ctx, child, &open, ViewportId::ROOT, 3.5);
+        ToastManager::with(&ctx, |manager| {
+            assert!(manager.get(id).is_none());
+            let history: Vec<ToastId> = manager.history().iter().map(|entry| entry.id).collect();
+            assert_eq!(history, [id]);
+        });
+    }
+
+    #[test]
+    fn toasts_of_a_closed_viewport_move_to_the_focused_one() {
+        let ctx = Context::default();
+        let child = ViewportId::from_hash_of("child");
+        let later = ViewportId::from_hash_of("later");
+        let id = ToastManager::add(&ctx, Toast::info("Saved").persistent());
+        let waiting = ToastManager::add(&ctx, Toast::info("Synced").viewport(later));
+
+        run_in(&ctx, child, &[ViewportId::ROOT, child], child, 0.0);
+        assert_eq!(assigned(&ctx, id), Some(child));
+
+        run_in(
+            &ctx,
+            ViewportId::ROOT,
+            &[ViewportId::ROOT],
+            ViewportId::ROOT,
+            1.0,
+        );
+        assert_eq!(assigned(&ctx, id), Some(ViewportId::ROOT));
+        assert_eq!(assigned(&ctx, waiting), Some(later));
+    }
+
+    #[test]
+    fn changes_to_toasts_being_rendered_are_applied_afterwards() {
+        let ctx = Context::default();
+        let sender = ToastManager::sender(&ctx);
+        let child = ViewportId::from_hash_of("child");
+        let open = [ViewportId::ROOT, child];
+        let upload = ToastManager::add(&ctx, Toast::info("Uploading…").persistent());
+        let stale = ToastManager::add(&ctx, Toast::info("Offline").persistent());
+        let elsewhere = ToastManager::add(&ctx, Toast::info("Synced").viewport(child));
+        // The custom body runs while the root toasts are taken out, like a worker thread mid-frame.
+        let results = Arc::new(Mutex::new(Vec::new()));
+        let sink = results.clone();
+        ToastManager::add(
+            &ctx,
+            Toast::custom(move |_ui| {
+                let mut results = sink.lock().unwrap();
+                if results.is_empty() {
+                    results.push(sender.update(upload, |toast| toast.message("Uploaded")));
+                    results.push(sender.dismiss(stale));
+                    results.push(sender.update(elsewhere, |toast| toast.message("Sent")));
+                    results.push(sender.dismiss(ToastId(99)));
+                }
+            })
+            .persistent(),
+        );
+
+        run_in(&ctx, ViewportId::ROOT, &open, ViewportId::ROOT, 0.0);
+        assert_eq!(*results.lock().unwrap(), [true, true, true, false]);
+        ToastManager::with(&ctx, |manager| {
+            assert_eq!(manager.len(), 4);
+            assert_eq!(manager.get(upload).unwrap().message, "Uploaded");
+            assert!(manager.get(stale).unwrap().is_dismissed());
+            assert_eq!(manager.get(elsewhere).unwrap().message, "Sent");
+        });
+    }
+
+    #[test]
+    fn dismiss_all_skips_toasts_already_leaving() {
+        let ctx = Context::default();
+        ToastManager::with(&ctx, |manager| manager.reduce_motion = true);
+        let expired =
+            ToastManager::add(&ctx, Toast::info("Saved").duration(Duration::from_secs(1)));
+        let live = ToastManager::add(&ctx, Toast::info("Synced").persistent());
+        run(&ctx, 0.0, ToastManager::show);
+        run(&ctx, 1.5, ToastManager::show);
+
+        let input = egui::RawInput {
+            time: Some(1.6),
+            events: vec![egui::Event::Key {
+                key: Key::Escape,
+                physical_key: None,
+                pressed: true,
+                repeat: false,
+                modifiers: Modifiers::SHIFT,
+            }],
+            ..Default::default()
+        };
+        let mut events = Vec::new();
+        let _ = ctx.run(input, |ctx| {
+            ToastManager::show(ctx);
+            events = ToastManager::events(ctx);
+        });
+        assert_eq!(events, [ToastEvent::Dismissed { id: live }]);
+
+        run(&ctx, 1.7, ToastManager::show);
+        ToastManager::with(&ctx, |manager| {
+            let history: Vec<(ToastId, bool)> = manager
+                .history()
+                .iter()
+                .map(|entry| (entry.id, entry.dismissed))
+                .collect();
+            assert_eq!(history, [(expired, false), (live, true)]);
+            assert_eq!(manager.unread_count(), 1);
+        });
+    }
+
+    #[test]
+    fn repeats_coalesce_and_restart_the_countdown() {
+        let mut manager = ToastManager::default();
+        let id = manager.push(Toast::info("Connection lost"));
+        let toast = manager.get_mut(id).unwrap();
+        toast.tick(0.0);
+        toast.tick(3.0);
+        assert_eq!(toast.remaining(), Some(Duration::from_secs(1)));
+
+        assert_eq!(manager.push(Toast::info("Connection lost")), id);
+        assert_eq!(manager.push(Toast::info("Connection lost")), id);
+        assert_eq!(manager.len(), 1);
+        let toast = manager.get(id).unwrap();
+        assert_eq!(toast.repeat_count(), 3);
+        assert_eq!(toast.remaining(), Some(Duration::from_secs(4)));
+    }
+
+    #[test]
+    fn only_matching_keys_coalesce() {
+        let mut manager = ToastManager::default();
+        let info = manager.push(Toast::info("Connection lost"));
+        assert_ne!(manager.push(Toast::error("Connection lost")), info);
+        assert_ne!(
+            manager.push(Toast::info("Connection lost").dedupe(false)),
+            info
+        );
+        let keyed = manager.push(Toast::info("Retrying in 5s").dedupe_key("retry"));
+        assert_eq!(
+            manager.push(Toast::info("Retrying in 4s").dedupe_key("retry")),
+            keyed
+        );
+        assert_eq!(manager.get(keyed).unwrap().message, "Retrying in 4s");
+        assert_eq!(manager.len(), 4);
+    }
+
+    #[test]
+    fn different_titles_and_icons_do_not_coalesce() {
+        let mut manager = ToastManager::default();
+        let upload = manager.push(Toast::error("Failed").title("Upload"));
+        assert_ne!(
+            manager.push(Toast::error("Failed").title("Download")),
+            upload
+        );
+        assert_ne!(
+            manager.push(Toast::error("Failed").title("Upload").icon("⛔")),
+            upload
+        );
+        assert_eq!(manager.push(Toast::error("Failed").title("Upload")), upload);
+        assert_eq!(manager.len(), 3);
+    }
+
+    #[test]
+    fn finished_toasts_and_other_viewports_do_not_coalesce() {
+        let mut manager = ToastManager::default();
+        let dismissed = manager.push(Toast::info("Saved"));
+        manager.get_mut(dismissed).unwrap().dismiss();
+        assert_ne!(manager.push(Toast::info("Saved")), dismissed);
+
+        let other = ViewportId::from_hash_of("other");
+        let elsewhere = manager.push(Toast::info("Synced").viewport(other));
+        assert_ne!(manager.push(Toast::info("Synced")), elsewhere);
+        assert_eq!(
+            manager.push(Toast::info("Synced").viewport(other)),
+            elsewhere
+        );
+    }
+
+    /// A managed toast that has expired, or was dismissed if `dismissed` is set.
+    fn finished(id: u64, dismissed: bool) -> ManagedToast {
+        let mut toast = Toast::new(&format!("Toast {id}")).duration(Duration::from_secs(1));
+        toast.tick(0.0);
+        if dismissed {
+            toast.dismiss();
+        } else {
+            toast.tick(2.0);
+        }
+        ManagedToast::new(ToastId(id), toast)
+    }
+
+    #[test]
+    fn history_drops_the_oldest_entries_beyond_the_limit() {
+        let mut manager = ToastManager {
+            history_limit: 2,
+            ..Default::default()
+        };
+        for id in 0..3 {
+            manager.record(finished(id, false));
+        }
+        let ids: Vec<u64> = manager.history().iter().map(|entry| entry.id.0).collect();
+        assert_eq!(ids, [1, 2]);
+
+        manager.history_limit = 0;
+        manager.record(finished(3, false));
+        assert!(manager.history().is_empty());
+    }
+
+    #[test]
+    fn dismissed_entries_start_out_read() {
+        let mut manager = ToastManager::default();
+        manager.record(finished(0, true));
+        manager.record(finished(1, false));
+        manager.record(finished(2, false));
+        let entries: Vec<(bool, bool)> = manager
+            .history()
+            .iter()
+            .map(|entry| (entry.dismissed, entry.read))
+            .collect();
+        assert_eq!(entries, [(true, true), (false, false), (false, false)]);
+        assert_eq!(manager.unread_count(), 2);
+
+        assert!(manager.mark_read(ToastId(1)));
+        assert!(!manager.mark_read(ToastId(7)));
+        assert_eq!(manager.unread_count(), 1);
+        manager.mark_all_read();
+        assert_eq!(manager.unread_count(), 0);
+    }
+}
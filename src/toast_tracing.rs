@@ -0,0 +1,263 @@
+//! # Toast Tracing Module
+//!
+//! This module bridges `tracing` into toasts. The [`ToastLayer`] is a [`tracing_subscriber::Layer`] that
+//! turns events into toasts, so failures in instrumented code reach users without changing that code.
+//!
+//! ## Usage
+//!
+//! Create the layer from a [`ToastSender`] and add it to a subscriber next to the other layers. The
+//! event's `message` field becomes the toast text, and the level maps to the toast level: `ERROR`
+//! becomes an error toast, `WARN` a warning toast and anything below an info toast. Events without a
+//! message are skipped.
+//!
+//! Which events show up is controlled by filters, all of which must pass:
+//! - [`ToastLayer::level`] sets the minimum level, `WARN` by default.
+//! - [`ToastLayer::target`] restricts the events to targets starting with one of the given prefixes.
+//! - [`ToastLayer::filter`] adds a custom check on the event metadata.
+//!
+//! Events from egui's own crates are never turned into toasts, as they may be emitted while the egui
+//! context is locked.
+//!
+//! ## Example
+//! ```no_run
+//! # let ctx = egui::Context::default();
+//! use egui_widget_ext::{ToastLayer, ToastManager};
+//! use tracing::Level;
+//! use tracing_subscriber::layer::SubscriberExt;
+//!
+//! let layer = ToastLayer::new(ToastManager::sender(&ctx))
+//!     .level(Level::WARN)
+//!     .target("my_service");
+//! let subscriber = tracing_subscriber::registry().with(layer);
+//! tracing::subscriber::set_global_default(subscriber).expect("no other subscriber installed");
+//!
+//! tracing::error!(target: "my_service::sync", "Could not reach the server");
+//! ```
+//!
+//! ## Components
+//! - [`ToastLayer`]: Struct implementing [`tracing_subscriber::Layer`] that posts toasts.
+
+use std::fmt;
+
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::toast_manager::is_egui_target;
+use crate::{Toast, ToastSender};
+
+/// Custom check deciding if an event is turned into a toast.
+type EventFilter = Box<dyn Fn(&Metadata<'_>) -> bool + Send + Sync>;
+
+/// A [`tracing_subscriber::Layer`] that turns events into toasts.
+///
+/// Events passing the level, target and custom filters are posted through a [`ToastSender`].
+pub struct ToastLayer {
+    /// Sender used to post the toasts.
+    sender: ToastSender,
+    /// Minimum level of the events turned into toasts.
+    level: Level,
+    /// Target prefixes of the events turned into toasts, all targets when empty.
+    targets: Vec<String>,
+    /// Custom check on the event metadata.
+    filter: Option<EventFilter>,
+}
+
+impl fmt::Debug for ToastLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToastLayer")
+            .field("sender", &self.sender)
+            .field("level", &self.level)
+            .field("targets", &self.targets)
+            .field("filter", &self.filter.is_some())
+            .finish()
+    }
+}
+
+impl ToastLayer {
+    /// Create a layer posting `WARN` and `ERROR` events from any target through the given sender.
+    pub fn new(sender: ToastSender) -> Self {
+        Self {
+            sender,
+            level: Level::WARN,
+            targets: Vec::new(),
+            filter: None,
+        }
+    }
+
+    /// Set the minimum level of the events turned into toasts.
+    pub fn level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Only turn events into toasts whose target starts with one of the given prefixes.
+    ///
+    /// Can be called multiple times to allow several targets.
+    pub fn target(mut self, prefix: &str) -> Self {
+        self.targets.push(prefix.to_string());
+        self
+    }
+
+    /// Set a custom check on the event metadata, which must return `true` for the event to show up.
+    pub fn filter(
+        mut self,
+        filter: impl Fn(&Metadata<'_>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Check if an event with the given metadata is turned into a toast.
+    fn shows(&self, metadata: &Metadata<'_>) -> bool {
+        let target = metadata.target();
+        *metadata.level() <= self.level
+            && !is_egui_target(target)
+            && (self.targets.is_empty()
+                || self.targets.iter().any(|prefix| target.starts_with(prefix)))
+            && self.filter.as_ref().is_none_or(|filter| filter(metadata))
+    }
+}
+
+impl<S: Subscriber> Layer<S> for ToastLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if !self.shows(metadata) {
+            return;
+        }
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let Some(message) = visitor.message else {
+            return;
+        };
+        let toast = match *metadata.level() {
+            Level::ERROR => Toast::error(&message),
+            Level::WARN => Toast::warning(&message),
+            _ => Toast::info(&message),
+        };
+        self.sender.send(toast);
+    }
+}
+
+/// Field visitor extracting the `message` field of an event.
+#[derive(Default)]
+struct MessageVisitor {
+    /// The formatted message, if the event has one.
+    message: Option<String>,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{value:?}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+    use crate::{AlertLevel, ToastManager};
+
+    /// Emit events with a configured layer installed for the current thread and collect the toasts.
+    fn toasts(
+        configure: impl FnOnce(ToastLayer) -> ToastLayer,
+        emit: impl FnOnce(),
+    ) -> Vec<(String, Option<AlertLevel>)> {
+        let ctx = egui::Context::default();
+        let layer = configure(ToastLayer::new(ToastManager::sender(&ctx)));
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), emit);
+        ToastManager::with(&ctx, |manager| {
+            manager
+                .toasts()
+                .map(|toast| (toast.message.clone(), toast.level))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn levels_map_to_toast_levels() {
+        let posted = toasts(
+            |layer| layer.level(Level::INFO),
+            || {
+                tracing::error!("Disk failed");
+                tracing::warn!("Disk almost full");
+                tracing::info!("Disk checked");
+                tracing::debug!("Disk polled");
+            },
+        );
+        assert_eq!(
+            posted,
+            [
+                ("Disk failed".to_string(), Some(AlertLevel::Error)),
+                ("Disk almost full".to_string(), Some(AlertLevel::Warning)),
+                ("Disk checked".to_string(), Some(AlertLevel::Info)),
+            ]
+        );
+    }
+
+    #[test]
+    fn level_threshold_filters_events() {
+        let posted = toasts(
+            |layer| layer,
+            || {
+                tracing::warn!("Disk almost full");
+                tracing::info!("Disk checked");
+            },
+        );
+        assert_eq!(
+            posted,
+            [("Disk almost full".to_string(), Some(AlertLevel::Warning))]
+        );
+    }
+
+    #[test]
+    fn target_prefixes_filter_events() {
+        let posted = toasts(
+            |layer| layer.target("app::sync").target("app::upload"),
+            || {
+                tracing::warn!(target: "app::sync::worker", "Sync stalled");
+                tracing::warn!(target: "app::ui", "Slow frame");
+                tracing::warn!(target: "app::upload", "Upload retried");
+            },
+        );
+        let messages: Vec<&str> = posted.iter().map(|(message, _)| message.as_str()).collect();
+        assert_eq!(messages, ["Sync stalled", "Upload retried"]);
+    }
+
+    #[test]
+    fn custom_filter_is_applied() {
+        let posted = toasts(
+            |layer| layer.filter(|metadata| !metadata.target().ends_with("::noisy")),
+            || {
+                tracing::warn!(target: "app::noisy", "Retrying");
+                tracing::warn!(target: "app::sync", "Sync stalled");
+            },
+        );
+        let messages: Vec<&str> = posted.iter().map(|(message, _)| message.as_str()).collect();
+        assert_eq!(messages, ["Sync stalled"]);
+    }
+
+    #[test]
+    fn egui_targets_and_events_without_message_are_skipped() {
+        let posted = toasts(
+            |layer| layer,
+            || {
+                tracing::error!(target: "egui::context", "Context locked");
+                tracing::error!(target: "epaint", "Font missing");
+                tracing::error!(code = 3);
+                tracing::error!(target: "egui_widget_ext", "Toast failed");
+            },
+        );
+        let messages: Vec<&str> = posted.iter().map(|(message, _)| message.as_str()).collect();
+        assert_eq!(messages, ["Toast failed"]);
+    }
+}